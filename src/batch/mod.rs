@@ -10,6 +10,7 @@ use lsm_tree::{AbstractTree, ValueType};
 use std::{
     collections::{HashMap, HashSet},
     sync::Arc,
+    time::Duration,
 };
 
 /// Partition key (a.k.a. column family, locality group)
@@ -79,6 +80,14 @@ impl Batch {
         ));
     }
 
+    /// Commits the batch to the [`Keyspace`] atomically on an I/O thread
+    ///
+    /// See [`Batch::commit`].
+    #[cfg(feature = "async")]
+    pub fn commit_async(self) -> crate::io_pool::IoFuture<crate::Result<()>> {
+        crate::io_pool::spawn_blocking(move || self.commit())
+    }
+
     /// Commits the batch to the [`Keyspace`] atomically
     ///
     /// # Errors
@@ -87,20 +96,42 @@ impl Batch {
     pub fn commit(mut self) -> crate::Result<()> {
         use std::sync::atomic::Ordering;
 
+        self.keyspace
+            .write_buffer_manager
+            .check_saturation(&self.keyspace.config)?;
+
         log::trace!("batch: Acquiring journal writer");
-        let mut journal_writer = self.keyspace.journal.get_writer();
+        let mut journal_writer = self.keyspace.journal.try_get_writer()?;
 
         // IMPORTANT: Check the poisoned flag after getting journal mutex, otherwise TOCTOU
         if self.keyspace.is_poisoned.load(Ordering::Relaxed) {
             return Err(crate::Error::Poisoned);
         }
 
+        if journal_writer.is_closed {
+            return Err(crate::Error::KeyspaceClosed);
+        }
+
         let batch_seqno = self.keyspace.seqno.next();
 
         let _ = journal_writer.write_batch(self.data.iter(), self.data.len(), batch_seqno);
+        let journal_ticket = journal_writer.appended_count();
 
+        drop(journal_writer);
+
+        // NOTE: Persist outside of the journal lock, so concurrent commits can share a sync
         if let Some(mode) = self.durability {
-            if let Err(e) = journal_writer.persist(mode) {
+            let window = self
+                .keyspace
+                .config
+                .group_commit_window_us
+                .map(|us| Duration::from_micros(us.into()));
+
+            if let Err(e) = self
+                .keyspace
+                .journal
+                .persist_batch(journal_ticket, mode, window)
+            {
                 self.keyspace.is_poisoned.store(true, Ordering::Release);
 
                 log::error!(
@@ -111,8 +142,6 @@ impl Batch {
             }
         }
 
-        drop(journal_writer);
-
         // NOTE: Fully (write) lock, so the batch can be committed atomically
         log::trace!("batch: Acquiring partitions lock");
         let partitions = self.keyspace.partitions.write().expect("lock is poisoned");
@@ -181,6 +210,13 @@ impl Batch {
         // Otherwise write buffer growth is unbounded when using batches
         self.keyspace.write_buffer_manager.allocate(batch_size);
 
+        // NOTE: The journal is shared, so checking any partition is enough
+        if let Some(partition) = partitions_with_possible_stall.iter().next() {
+            if let Err(e) = partition.check_journal_rotation() {
+                log::error!("Failed journal rotate check: {e:?}");
+            }
+        }
+
         // Check each affected partition for write stall/halt
         for partition in partitions_with_possible_stall {
             let memtable_size = partition.tree.active_memtable_size();
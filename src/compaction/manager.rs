@@ -73,6 +73,11 @@ impl CompactionManager {
         self.semaphore.release();
     }
 
+    #[cfg(test)]
+    pub fn len(&self) -> usize {
+        self.partitions.lock().expect("lock is poisoned").len()
+    }
+
     pub fn pop(&self) -> Option<PartitionHandle> {
         let mut lock = self.partitions.lock().expect("lock is poisoned");
         lock.pop_front()
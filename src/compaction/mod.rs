@@ -3,12 +3,25 @@
 // (found in the LICENSE-* files in the repository)
 
 pub(crate) mod manager;
+pub(crate) mod range;
 pub(crate) mod worker;
 
+use lsm_tree::compaction::{Choice, CompactionStrategy};
 use std::sync::Arc;
 
 pub use lsm_tree::compaction::{Fifo, Leveled, Levelled, SizeTiered};
 
+/// Result of a manual compaction, see [`crate::PartitionHandle::compact_manually`]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[allow(clippy::module_name_repetitions)]
+pub struct ManualCompactionReport {
+    /// Amount of segments that were merged into new segments
+    pub segments_merged: usize,
+
+    /// Amount of disk space that was freed, in bytes
+    pub bytes_reclaimed: u64,
+}
+
 /// Compaction strategy
 #[derive(Clone)]
 #[allow(clippy::module_name_repetitions)]
@@ -44,11 +57,57 @@ impl Default for Strategy {
 }
 
 impl Strategy {
-    pub(crate) fn inner(&self) -> Arc<dyn lsm_tree::compaction::CompactionStrategy + Send + Sync> {
+    pub(crate) fn inner(&self) -> Arc<dyn CompactionStrategy + Send + Sync> {
         match self {
             Self::Leveled(s) => Arc::new(s.clone()),
             Self::SizeTiered(s) => Arc::new(s.clone()),
             Self::Fifo(s) => Arc::new(s.clone()),
         }
     }
+
+    /// Returns the size of segments written by the strategy's merges.
+    pub(crate) fn target_size(&self) -> u64 {
+        match self {
+            Self::Leveled(s) => s.target_size.into(),
+            Self::SizeTiered(s) => s.base_size.into(),
+            Self::Fifo(_) => u64::MAX,
+        }
+    }
+
+    /// Returns the strategy, overriding the size of segments written by merges, if given.
+    pub(crate) fn with_target_size(
+        &self,
+        target_size: Option<u64>,
+    ) -> Arc<dyn CompactionStrategy + Send + Sync> {
+        let inner = self.inner();
+
+        match target_size {
+            Some(target_size) => Arc::new(TargetSize { inner, target_size }),
+            None => inner,
+        }
+    }
+}
+
+/// Wraps a compaction strategy, replacing the target size of its merge outputs
+///
+/// The wrapped strategy still uses its own target size to shape the levels.
+struct TargetSize {
+    inner: Arc<dyn CompactionStrategy + Send + Sync>,
+    target_size: u64,
+}
+
+impl CompactionStrategy for TargetSize {
+    fn choose(
+        &self,
+        levels: &lsm_tree::level_manifest::LevelManifest,
+        config: &lsm_tree::Config,
+    ) -> Choice {
+        match self.inner.choose(levels, config) {
+            Choice::Merge(mut input) => {
+                input.target_size = self.target_size;
+                Choice::Merge(input)
+            }
+            choice => choice,
+        }
+    }
 }
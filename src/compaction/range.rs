@@ -0,0 +1,114 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+use crate::HashSet;
+use lsm_tree::{
+    compaction::{Choice, CompactionStrategy, Input},
+    level_manifest::LevelManifest,
+    SeqNo, UserKey,
+};
+use std::ops::Bound;
+
+/// Merges all segments overlapping a key range into the last level
+///
+/// The selection is widened until no other segment overlaps the key range
+/// of the selected segments, so the destination level stays disjoint and no
+/// unselected segment can hold versions that the merge would need to see.
+pub struct Strategy {
+    bounds: (Bound<UserKey>, Bound<UserKey>),
+    target_size: u64,
+    gc_watermark: SeqNo,
+}
+
+impl Strategy {
+    pub fn new(
+        bounds: (Bound<UserKey>, Bound<UserKey>),
+        target_size: u64,
+        gc_watermark: SeqNo,
+    ) -> Self {
+        Self {
+            bounds,
+            target_size,
+            gc_watermark,
+        }
+    }
+}
+
+impl CompactionStrategy for Strategy {
+    fn choose(&self, levels: &LevelManifest, _: &lsm_tree::Config) -> Choice {
+        let visible_ids = levels
+            .resolved_view()
+            .iter()
+            .flat_map(|level| level.segments.iter().map(|segment| segment.metadata.id))
+            .collect::<HashSet<_>>();
+
+        let mut selected = levels
+            .iter()
+            .filter(|segment| {
+                segment
+                    .metadata
+                    .key_range
+                    .overlaps_with_bounds(&self.bounds)
+            })
+            .collect::<Vec<_>>();
+
+        loop {
+            let Some(min) = selected
+                .iter()
+                .map(|segment| &segment.metadata.key_range.0)
+                .min()
+            else {
+                return Choice::DoNothing;
+            };
+
+            let max = selected
+                .iter()
+                .map(|segment| &segment.metadata.key_range.1)
+                .max()
+                .unwrap_or(min);
+
+            let bounds = (Bound::Included(min.clone()), Bound::Included(max.clone()));
+
+            let widened = levels
+                .iter()
+                .filter(|segment| segment.metadata.key_range.overlaps_with_bounds(&bounds))
+                .collect::<Vec<_>>();
+
+            if widened.len() == selected.len() {
+                break;
+            }
+
+            selected = widened;
+        }
+
+        // NOTE: Segments that are being compacted by another thread cannot be
+        // merged, and the other compaction may write into our key range
+        if selected
+            .iter()
+            .any(|segment| !visible_ids.contains(&segment.metadata.id))
+        {
+            log::debug!("range compaction: key range is busy, skipping");
+            return Choice::DoNothing;
+        }
+
+        // NOTE: Tombstones are dropped when merging into the last level, regardless
+        // of the GC watermark, so only go there if no snapshot can see any of the
+        // merged versions; otherwise a snapshot could lose data beneath a tombstone
+        let is_gc_safe = selected
+            .iter()
+            .all(|segment| segment.metadata.seqnos.1 < self.gc_watermark);
+
+        let last_level = levels.last_level_index();
+
+        Choice::Merge(Input {
+            segment_ids: selected.iter().map(|segment| segment.metadata.id).collect(),
+            dest_level: if is_gc_safe {
+                last_level
+            } else {
+                last_level.saturating_sub(1)
+            },
+            target_size: self.target_size,
+        })
+    }
+}
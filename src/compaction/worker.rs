@@ -3,7 +3,10 @@
 // (found in the LICENSE-* files in the repository)
 
 use super::manager::CompactionManager;
-use crate::snapshot_tracker::SnapshotTracker;
+use crate::{
+    event::{emit, Event},
+    snapshot_tracker::SnapshotTracker,
+};
 use lsm_tree::AbstractTree;
 
 /// Runs a single run of compaction.
@@ -17,14 +20,29 @@ pub fn run(compaction_manager: &CompactionManager, snapshot_tracker: &SnapshotTr
         item.0.name
     );
 
-    let strategy = item.config.compaction_strategy.clone();
+    let strategy = item.compaction_strategy();
 
     // TODO: loop if there's more work to do
 
+    emit(
+        &item.keyspace_config,
+        Event::CompactionStarted {
+            partition: item.name.clone(),
+        },
+    );
+
     if let Err(e) = item
         .tree
-        .compact(strategy.inner(), snapshot_tracker.get_seqno_safe_to_gc())
+        .compact(strategy, snapshot_tracker.get_seqno_safe_to_gc())
     {
         log::error!("Compaction failed: {e:?}");
+        return;
     };
+
+    emit(
+        &item.keyspace_config,
+        Event::CompactionFinished {
+            partition: item.name.clone(),
+        },
+    );
 }
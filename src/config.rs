@@ -2,13 +2,32 @@
 // This source code is licensed under both the Apache 2.0 and MIT License
 // (found in the LICENSE-* files in the repository)
 
-use crate::{journal::error::RecoveryMode, path::absolute_path, Keyspace};
-use lsm_tree::{descriptor_table::FileDescriptorTable, BlobCache, BlockCache};
+use crate::{
+    batch::PartitionKey,
+    event::{Event, EventListener},
+    journal::error::RecoveryMode,
+    path::absolute_path,
+    HashMap, Keyspace,
+};
+use lsm_tree::{descriptor_table::FileDescriptorTable, BlobCache, BlockCache, CompressionType};
 use std::{
     path::{Path, PathBuf},
     sync::Arc,
 };
 
+/// Behaviour of writes when the write buffer is saturated
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum WriteStallMode {
+    /// Block the writer until flushes have freed enough of the write buffer
+    #[default]
+    Block,
+
+    /// Reject the write with [`crate::Error::WriteBufferFull`]
+    ///
+    /// This allows callers to implement their own pacing, e.g. using [`Keyspace::write_buffer_size`].
+    Error,
+}
+
 /// Global keyspace configuration
 #[derive(Clone)]
 pub struct Config {
@@ -26,12 +45,24 @@ pub struct Config {
     #[doc(hidden)]
     pub blob_cache: Arc<BlobCache>,
 
+    /// Block caches of partitions that do not use the keyspace's block cache
+    pub(crate) partition_block_caches: HashMap<PartitionKey, Arc<BlockCache>>,
+
     /// Descriptor table that will be shared between partitions
     pub(crate) descriptor_table: Arc<FileDescriptorTable>,
 
     /// Max size of all journals in bytes
     pub(crate) max_journaling_size_in_bytes: u64, // TODO: should be configurable during runtime: AtomicU64
 
+    /// Size after which the active journal is sealed
+    pub(crate) journal_rotation_size: Option<u64>,
+
+    /// Compression of journal item values
+    pub(crate) journal_compression: CompressionType,
+
+    /// Size of segments written by compactions
+    pub(crate) segment_target_size: Option<u64>,
+
     /// Max size of all active memtables
     ///
     /// This can be used to cap the memory usage if there are
@@ -49,7 +80,35 @@ pub struct Config {
     /// Fsync every N ms asynchronously
     pub(crate) fsync_ms: Option<u16>,
 
+    /// Time a durable commit waits for concurrent commits to share its sync
+    pub(crate) group_commit_window_us: Option<u32>,
+
     pub(crate) journal_recovery_mode: RecoveryMode,
+
+    /// Distance (in seqnos) the GC watermark keeps from freed snapshots
+    pub(crate) snapshot_safety_gap: u64,
+
+    /// Max amount of concurrently open snapshots
+    pub(crate) max_open_snapshots: Option<usize>,
+
+    /// Max amount of committed transactions retained for conflict detection
+    #[cfg(feature = "ssi_tx")]
+    pub(crate) max_committed_txns: Option<usize>,
+
+    /// Fraction of the max journal size that triggers memtable flushes
+    pub(crate) journal_flush_watermark: f64,
+
+    /// Fraction of the max write buffer size that triggers memtable flushes
+    pub(crate) write_buffer_flush_watermark: f64,
+
+    /// Fraction of the max write buffer size at which writes are stalled
+    pub(crate) write_stall_threshold: f64,
+
+    /// Behaviour of writes when the write buffer is saturated
+    pub(crate) write_stall_mode: WriteStallMode,
+
+    /// Callback for background maintenance events
+    pub(crate) event_listener: Option<Arc<EventListener>>,
 }
 
 const DEFAULT_CPU_CORES: usize = 4;
@@ -79,14 +138,28 @@ impl Default for Config {
             clean_path_on_drop: false,
             block_cache: Arc::new(BlockCache::with_capacity_bytes(/* 16 MiB */ 16 * 1_024 * 1_024)),
             blob_cache: Arc::new(BlobCache::with_capacity_bytes(/* 16 MiB */ 16 * 1_024 * 1_024)),
+            partition_block_caches: HashMap::default(),
             descriptor_table: Arc::new(FileDescriptorTable::new(get_open_file_limit(), 4)),
             max_write_buffer_size_in_bytes: /* 64 MiB */ 64 * 1_024 * 1_024,
             max_journaling_size_in_bytes: /* 512 MiB */ 512 * 1_024 * 1_024,
+            journal_rotation_size: None,
+            journal_compression: CompressionType::None,
+            segment_target_size: None,
             fsync_ms: None,
+            group_commit_window_us: None,
             flush_workers_count: cpus.min(4),
             compaction_workers_count: cpus.min(4),
             journal_recovery_mode: RecoveryMode::default(),
             manual_journal_persist: false,
+            snapshot_safety_gap: 50,
+            max_open_snapshots: None,
+            #[cfg(feature = "ssi_tx")]
+            max_committed_txns: None,
+            journal_flush_watermark: 0.5,
+            write_buffer_flush_watermark: 0.5,
+            write_stall_threshold: 0.9,
+            write_stall_mode: WriteStallMode::Block,
+            event_listener: None,
         }
     }
 }
@@ -113,7 +186,11 @@ impl Config {
 
     /// Sets the amount of flush workers
     ///
-    /// Default = # CPU cores
+    /// This is the maximum amount of sealed memtables (across all partitions)
+    /// that are flushed concurrently. Sealed memtables stay in the flush queue
+    /// (and count towards the write buffer) until their flush has finished.
+    ///
+    /// Default = # CPU cores (at most 4)
     #[must_use]
     pub fn flush_workers(mut self, n: usize) -> Self {
         self.flush_workers_count = n;
@@ -152,6 +229,45 @@ impl Config {
         self
     }
 
+    /// Sets the block cache of a single partition, instead of the keyspace's block cache.
+    ///
+    /// This allows giving hot partitions a larger cache, and prevents cold partitions
+    /// from evicting their blocks. The same cache can be passed for multiple partitions
+    /// to share it between them.
+    ///
+    /// Because caches cannot be persisted, the block cache needs to be set every time
+    /// the keyspace is opened.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fjall::{BlockCache, Config, PartitionCreateOptions};
+    /// # use std::sync::Arc;
+    /// #
+    /// # let folder = tempfile::tempdir()?;
+    /// let keyspace = Config::new(folder)
+    ///     .partition_block_cache("hot", Arc::new(BlockCache::with_capacity_bytes(64 * 1_024 * 1_024)))
+    ///     .open()?;
+    ///
+    /// let hot = keyspace.open_partition("hot", PartitionCreateOptions::default())?;
+    /// #
+    /// # Ok::<(), fjall::Error>(())
+    /// ```
+    #[must_use]
+    pub fn partition_block_cache(mut self, partition: &str, block_cache: Arc<BlockCache>) -> Self {
+        self.partition_block_caches
+            .insert(partition.into(), block_cache);
+        self
+    }
+
+    /// Returns the block cache that should be used by a partition.
+    pub(crate) fn block_cache_for(&self, partition: &str) -> Arc<BlockCache> {
+        self.partition_block_caches
+            .get(partition)
+            .unwrap_or(&self.block_cache)
+            .clone()
+    }
+
     /// Sets the blob cache.
     ///
     /// Defaults to a block cache with 16 MiB of capacity
@@ -182,6 +298,79 @@ impl Config {
         self
     }
 
+    /// Sets the fraction of the max journal size at which partitions are flushed
+    /// to allow evicting old journals.
+    ///
+    /// Lower values flush earlier, which keeps the journal smaller at the cost of
+    /// writing smaller segments.
+    ///
+    /// Default = 0.5
+    ///
+    /// # Panics
+    ///
+    /// Panics if the watermark is not in (0.0, 1.0].
+    #[must_use]
+    pub fn journal_flush_watermark(mut self, watermark: f64) -> Self {
+        assert!(
+            watermark > 0.0 && watermark <= 1.0,
+            "flush watermark should be in (0.0, 1.0]"
+        );
+
+        self.journal_flush_watermark = watermark;
+        self
+    }
+
+    /// Sets the fraction of the max write buffer size at which memtables are flushed.
+    ///
+    /// Lower values flush earlier, which reduces memory usage on constrained machines;
+    /// higher values allow larger memtables before they are flushed.
+    ///
+    /// Default = 0.5
+    ///
+    /// # Panics
+    ///
+    /// Panics if the watermark is not in (0.0, 1.0].
+    #[must_use]
+    pub fn write_buffer_flush_watermark(mut self, watermark: f64) -> Self {
+        assert!(
+            watermark > 0.0 && watermark <= 1.0,
+            "flush watermark should be in (0.0, 1.0]"
+        );
+
+        self.write_buffer_flush_watermark = watermark;
+        self
+    }
+
+    /// Sets the fraction of the max write buffer size at which writes
+    /// are slowed down to let flushes catch up.
+    ///
+    /// Only applies to [`WriteStallMode::Block`].
+    ///
+    /// Default = 0.9
+    ///
+    /// # Panics
+    ///
+    /// Panics if the threshold is not in (0.0, 1.0].
+    #[must_use]
+    pub fn write_stall_threshold(mut self, threshold: f64) -> Self {
+        assert!(
+            threshold > 0.0 && threshold <= 1.0,
+            "write stall threshold should be in (0.0, 1.0]"
+        );
+
+        self.write_stall_threshold = threshold;
+        self
+    }
+
+    /// Sets what happens to writes when the write buffer exceeds its max size.
+    ///
+    /// Default = [`WriteStallMode::Block`]
+    #[must_use]
+    pub fn write_stall_mode(mut self, mode: WriteStallMode) -> Self {
+        self.write_stall_mode = mode;
+        self
+    }
+
     /// Max size of all memtables in bytes.
     ///
     /// Similar to `db_write_buffer_size` in `RocksDB`, however it is disabled by default in `RocksDB`.
@@ -201,6 +390,68 @@ impl Config {
         self
     }
 
+    /// If Some, the active journal is sealed and a new journal is started
+    /// once the given amount of bytes has been written into it.
+    ///
+    /// Smaller journals are faster to replay one by one, and can be evicted
+    /// at a finer granularity once their data has been flushed.
+    ///
+    /// Default = off (journals are only rotated when a memtable is rotated)
+    ///
+    /// # Panics
+    ///
+    /// Panics if bytes is 0.
+    #[must_use]
+    pub fn journal_rotation_size(mut self, bytes: Option<u64>) -> Self {
+        if let Some(bytes) = bytes {
+            assert!(bytes > 0);
+        }
+
+        self.journal_rotation_size = bytes;
+        self
+    }
+
+    /// Sets the compression method of values written into the journal.
+    ///
+    /// Compressing the journal reduces its write amplification for large,
+    /// compressible values, at the cost of CPU time on every write and
+    /// during recovery.
+    ///
+    /// Each batch records its own compression, so this can be changed
+    /// between restarts without invalidating existing journals.
+    ///
+    /// Default = None
+    #[must_use]
+    pub fn journal_compression(mut self, compression: CompressionType) -> Self {
+        self.journal_compression = compression;
+        self
+    }
+
+    /// Sets the size of segments written by compactions, overriding the
+    /// target size of each partition's compaction strategy.
+    ///
+    /// Smaller segments give finer compaction granularity, at the cost of
+    /// more files (and file descriptors) per partition.
+    /// The compaction strategy still uses its own target size to decide
+    /// how large each level should grow.
+    ///
+    /// The target size is raised to at least 4 data blocks of the partition.
+    ///
+    /// Default = None (use the compaction strategy's target size)
+    ///
+    /// # Panics
+    ///
+    /// Panics if bytes is below 64 KiB.
+    #[must_use]
+    pub fn segment_target_size(mut self, bytes: Option<u64>) -> Self {
+        if let Some(bytes) = bytes {
+            assert!(bytes >= 64 * 1_024, "segment target size is too small");
+        }
+
+        self.segment_target_size = bytes;
+        self
+    }
+
     /// If Some, starts an fsync thread that asynchronously
     /// persists data to disk (using fsync).
     ///
@@ -219,6 +470,122 @@ impl Config {
         self
     }
 
+    /// If Some, durable commits (write batches or transactions with a
+    /// [`crate::PersistMode::SyncData`] or [`crate::PersistMode::SyncAll`] durability)
+    /// wait the given amount of microseconds before syncing the journal.
+    ///
+    /// Other commits appended to the journal in the meantime are synced
+    /// together, so N concurrent commits incur far fewer than N syncs,
+    /// at the cost of added commit latency.
+    ///
+    /// Even without a window, commits waiting on an in-progress sync are
+    /// grouped into the next sync.
+    ///
+    /// Default = off
+    ///
+    /// # Panics
+    ///
+    /// Panics if micros is 0.
+    #[must_use]
+    pub fn group_commit_window_us(mut self, micros: Option<u32>) -> Self {
+        if let Some(micros) = micros {
+            assert!(micros > 0);
+        }
+
+        self.group_commit_window_us = micros;
+        self
+    }
+
+    /// Sets the snapshot safety gap.
+    ///
+    /// The snapshot tracker is garbage collected every `n` closed snapshots,
+    /// and only frees versions that are at least `n` sequence numbers older than
+    /// the snapshot that triggered the GC. Larger values keep old versions around for
+    /// longer, protecting snapshots that are opened at a slightly older instant.
+    ///
+    /// Default = 50
+    ///
+    /// # Panics
+    ///
+    /// Panics if n is 0.
+    #[must_use]
+    pub fn snapshot_safety_gap(mut self, n: u64) -> Self {
+        assert!(n > 0, "safety gap should be > 0");
+
+        self.snapshot_safety_gap = n;
+        self
+    }
+
+    /// Sets the upper limit of concurrently open snapshots.
+    ///
+    /// Snapshots that are kept open prevent old versions from being garbage collected,
+    /// so leaked snapshots silently grow disk usage.
+    /// Once the limit is reached, [`PartitionHandle::try_snapshot`](crate::PartitionHandle::try_snapshot)
+    /// returns [`Error::SnapshotLimitReached`](crate::Error::SnapshotLimitReached).
+    ///
+    /// Default = unlimited
+    ///
+    /// # Panics
+    ///
+    /// Panics if n is 0.
+    #[must_use]
+    pub fn max_open_snapshots(mut self, n: usize) -> Self {
+        assert!(n > 0);
+
+        self.max_open_snapshots = Some(n);
+        self
+    }
+
+    /// Sets the upper limit of committed transactions that are retained for conflict detection.
+    ///
+    /// Committed transactions are retained as long as a snapshot that started before
+    /// their commit is open, so a long-lived snapshot lets them pile up.
+    /// Once the limit is reached, the oldest ones are dropped, and transactions
+    /// that started before a dropped one conservatively fail with a conflict.
+    ///
+    /// Default = unlimited
+    ///
+    /// # Panics
+    ///
+    /// Panics if n is 0.
+    #[cfg(feature = "ssi_tx")]
+    #[must_use]
+    pub fn max_committed_txns(mut self, n: usize) -> Self {
+        assert!(n > 0);
+
+        self.max_committed_txns = Some(n);
+        self
+    }
+
+    /// Registers a callback that is called when flushes and compactions start and finish.
+    ///
+    /// The callback runs on the background worker threads, so it should return quickly.
+    /// No internal locks are held while it runs.
+    ///
+    /// Default = none
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fjall::{Config, Event};
+    /// #
+    /// # let folder = tempfile::tempdir()?;
+    /// let keyspace = Config::new(folder)
+    ///     .on_event(|event| {
+    ///         if let Event::FlushFinished { partition, bytes, .. } = event {
+    ///             log::info!("flushed {bytes} bytes into {partition:?}");
+    ///         }
+    ///     })
+    ///     .open()?;
+    /// #
+    /// # Ok::<(), fjall::Error>(())
+    /// ```
+    #[must_use]
+    pub fn on_event<F: Fn(Event) + Send + Sync + 'static>(mut self, callback: F) -> Self {
+        self.event_listener = Some(Arc::new(callback));
+        self
+    }
+
     /// Opens a keyspace using the config.
     ///
     /// # Errors
@@ -37,11 +37,54 @@ pub enum Error {
 
     /// Partition is deleted
     PartitionDeleted,
+
+    /// Too many snapshots are open, see `Config::max_open_snapshots`
+    SnapshotLimitReached,
+
+    /// The requested snapshot seqno is below the GC watermark,
+    /// so versions it would read may have been dropped already
+    SnapshotTooOld,
+
+    /// The write buffer is saturated, see `Config::write_stall_mode`
+    ///
+    /// The write was not applied and can be retried after flushes have caught up.
+    WriteBufferFull,
+
+    /// Keys passed to `PartitionHandle::ingest` were not strictly ascending
+    UnsortedIngestion,
+
+    /// The keyspace folder is already opened by another keyspace,
+    /// either in another process or in this process
+    AlreadyOpen,
+
+    /// The keyspace was closed, see `Keyspace::close`
+    KeyspaceClosed,
+
+    /// The partition has a reverse index, so it can only be opened
+    /// using `Keyspace::open_indexed_partition`
+    PartitionIndexed,
 }
 
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "FjallError: {self:?}")
+        match self {
+            Self::Storage(e) => write!(f, "storage error: {e}"),
+            Self::Io(e) => write!(f, "I/O error: {e}"),
+            Self::Encode(e) => write!(f, "serialization failed: {e}"),
+            Self::Decode(e) => write!(f, "deserialization failed: {e}"),
+            Self::JournalRecovery(e) => write!(f, "journal recovery failed: {e:?}"),
+            Self::InvalidVersion(Some(version)) => write!(f, "invalid version: {version}"),
+            Self::InvalidVersion(None) => write!(f, "invalid version"),
+            Self::Poisoned => write!(f, "keyspace is poisoned"),
+            Self::PartitionDeleted => write!(f, "partition is deleted"),
+            Self::SnapshotLimitReached => write!(f, "too many snapshots are open"),
+            Self::SnapshotTooOld => write!(f, "snapshot is too old"),
+            Self::WriteBufferFull => write!(f, "write buffer is full"),
+            Self::UnsortedIngestion => write!(f, "ingested keys are not sorted"),
+            Self::AlreadyOpen => write!(f, "keyspace is already open"),
+            Self::KeyspaceClosed => write!(f, "keyspace is closed"),
+            Self::PartitionIndexed => write!(f, "partition is indexed"),
+        }
     }
 }
 
@@ -69,7 +112,48 @@ impl From<lsm_tree::Error> for Error {
     }
 }
 
-impl std::error::Error for Error {}
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Storage(e) => Some(e),
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
 
 /// Result helper type
 pub type Result<T> = std::result::Result<T, Error>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error as _;
+    use test_log::test;
+
+    #[test]
+    fn error_io_display_and_source() {
+        let error = Error::from(std::io::Error::from_raw_os_error(2));
+
+        let message = error.to_string();
+        assert!(message.starts_with("I/O error: "));
+        assert!(message.contains("os error 2"));
+
+        let source = error.source().expect("should have source");
+        assert_eq!(
+            Some(std::io::ErrorKind::NotFound),
+            source
+                .downcast_ref::<std::io::Error>()
+                .map(std::io::Error::kind),
+        );
+    }
+
+    #[test]
+    fn error_storage_source() {
+        let error = Error::from(lsm_tree::Error::Io(std::io::Error::from(
+            std::io::ErrorKind::NotFound,
+        )));
+        assert!(error.source().is_some());
+        assert!(Error::Poisoned.source().is_none());
+    }
+}
@@ -0,0 +1,55 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+use crate::{batch::PartitionKey, config::Config};
+use lsm_tree::SegmentId;
+
+/// Callback that receives background maintenance events
+pub type EventListener = dyn Fn(Event) + Send + Sync + 'static;
+
+/// Background maintenance event
+///
+/// See [`Config::on_event`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Event {
+    /// A partition started flushing its sealed memtables
+    FlushStarted {
+        /// Partition name
+        partition: PartitionKey,
+    },
+
+    /// A segment was written by a flush and registered in the partition
+    FlushFinished {
+        /// Partition name
+        partition: PartitionKey,
+
+        /// ID of the created segment
+        segment_id: SegmentId,
+
+        /// Size of the created segment on disk
+        bytes: u64,
+    },
+
+    /// A partition started running its compaction strategy
+    CompactionStarted {
+        /// Partition name
+        partition: PartitionKey,
+    },
+
+    /// A partition finished running its compaction strategy
+    CompactionFinished {
+        /// Partition name
+        partition: PartitionKey,
+    },
+}
+
+/// Sends an event to the configured listener, if any.
+///
+/// IMPORTANT: Must not be called while holding any internal lock,
+/// because the listener may call back into the keyspace.
+pub fn emit(config: &Config, event: Event) {
+    if let Some(listener) = &config.event_listener {
+        listener(event);
+    }
+}
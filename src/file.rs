@@ -10,6 +10,7 @@ pub const JOURNALS_FOLDER: &str = "journals";
 pub const PARTITIONS_FOLDER: &str = "partitions";
 
 pub const FJALL_MARKER: &str = "version";
+pub const LOCK_FILE: &str = "LOCK";
 pub const PARTITION_DELETED_MARKER: &str = ".deleted";
 pub const PARTITION_CONFIG_FILE: &str = "config";
 
@@ -69,6 +69,19 @@ impl FlushManager {
             .collect()
     }
 
+    /// Returns the sealed memtables of a partition that are waiting to be flushed.
+    pub(crate) fn sealed_memtables(&self, partition_name: &str) -> Vec<Arc<Memtable>> {
+        self.queues
+            .get(partition_name)
+            .map(|queue| {
+                queue
+                    .iter()
+                    .map(|task| task.sealed_memtable.clone())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     /// Returns the amount of queues.
     pub(crate) fn queue_count(&self) -> usize {
         self.queues.len()
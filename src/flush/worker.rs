@@ -4,9 +4,13 @@
 
 use super::manager::{FlushManager, Task};
 use crate::{
-    batch::PartitionKey, compaction::manager::CompactionManager, journal::manager::JournalManager,
-    snapshot_tracker::SnapshotTracker, write_buffer_manager::WriteBufferManager, HashMap,
-    PartitionHandle,
+    batch::PartitionKey,
+    compaction::manager::CompactionManager,
+    event::{emit, Event},
+    journal::manager::JournalManager,
+    snapshot_tracker::SnapshotTracker,
+    write_buffer_manager::WriteBufferManager,
+    HashMap, PartitionHandle,
 };
 use lsm_tree::{AbstractTree, Segment, SeqNo};
 use std::sync::{Arc, RwLock};
@@ -108,6 +112,8 @@ fn run_multi_flush(
 }
 
 /// Runs flush logic.
+///
+/// Errors are logged; the first one is returned, after all tasks have been tried.
 #[allow(clippy::too_many_lines)]
 pub fn run(
     flush_manager: &Arc<RwLock<FlushManager>>,
@@ -116,7 +122,7 @@ pub fn run(
     write_buffer_manager: &WriteBufferManager,
     snapshot_tracker: &SnapshotTracker,
     parallelism: usize,
-) {
+) -> crate::Result<()> {
     log::debug!("write locking flush manager");
     let mut fm = flush_manager.write().expect("lock is poisoned");
     let partitioned_tasks = fm.collect_tasks(parallelism);
@@ -126,7 +132,20 @@ pub fn run(
 
     if task_count == 0 {
         log::debug!("No tasks collected");
-        return;
+        return Ok(());
+    }
+
+    let mut first_error = None;
+
+    for (partition_name, tasks) in &partitioned_tasks {
+        if let Some(task) = tasks.first() {
+            emit(
+                &task.partition.keyspace_config,
+                Event::FlushStarted {
+                    partition: partition_name.clone(),
+                },
+            );
+        }
     }
 
     for result in run_multi_flush(&partitioned_tasks, snapshot_tracker.get_seqno_safe_to_gc()) {
@@ -140,6 +159,7 @@ pub fn run(
                 // otherwise we could cover up an unwritten journal, which will result in data loss
                 if let Err(e) = partition.tree.register_segments(&created_segments) {
                     log::error!("Failed to register segments: {e:?}");
+                    first_error.get_or_insert_with(|| e.into());
                 } else {
                     log::debug!("write locking flush manager to submit results");
                     let mut flush_manager = flush_manager.write().expect("lock is poisoned");
@@ -150,6 +170,18 @@ pub fn run(
                         created_segments.len()
                     );
                     flush_manager.dequeue_tasks(partition.name.clone(), created_segments.len());
+                    drop(flush_manager);
+
+                    for segment in &created_segments {
+                        emit(
+                            &partition.keyspace_config,
+                            Event::FlushFinished {
+                                partition: partition.name.clone(),
+                                segment_id: segment.metadata.id,
+                                bytes: segment.metadata.file_size,
+                            },
+                        );
+                    }
 
                     write_buffer_manager.free(memtables_size);
                     compaction_manager.notify(partition);
@@ -157,6 +189,7 @@ pub fn run(
             }
             Err(e) => {
                 log::error!("Flush error: {e:?}");
+                first_error.get_or_insert(e);
             }
         }
     }
@@ -168,7 +201,10 @@ pub fn run(
         .maintenance()
     {
         log::error!("journal GC failed: {e:?}");
+        first_error.get_or_insert(e);
     }
 
     log::debug!("fully done");
+
+    first_error.map_or(Ok(()), Err)
 }
@@ -0,0 +1,260 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+use std::{
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    sync::{mpsc, Arc, Condvar, Mutex, OnceLock, PoisonError},
+    task::{Context, Poll, Waker},
+};
+
+/// Amount of items a stream reads ahead of its consumer
+const STREAM_BUFFER_SIZE: usize = 64;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Threads that run blocking calls on behalf of futures
+///
+/// The pool is shared by all keyspaces and started on first use,
+/// so async executors never block on disk I/O.
+struct IoPool {
+    sender: mpsc::Sender<Job>,
+}
+
+impl IoPool {
+    fn get() -> &'static Self {
+        static POOL: OnceLock<IoPool> = OnceLock::new();
+
+        POOL.get_or_init(|| {
+            let (sender, receiver) = mpsc::channel::<Job>();
+            let receiver = Arc::new(Mutex::new(receiver));
+
+            let thread_count = std::thread::available_parallelism().map_or(4, usize::from);
+
+            log::debug!("Starting {thread_count} I/O threads");
+
+            for idx in 0..thread_count {
+                let receiver = receiver.clone();
+
+                std::thread::Builder::new()
+                    .name(format!("io-{idx}"))
+                    .spawn(move || loop {
+                        let job = receiver
+                            .lock()
+                            .unwrap_or_else(PoisonError::into_inner)
+                            .recv();
+
+                        let Ok(job) = job else {
+                            return;
+                        };
+
+                        // NOTE: A panicking job drops its sender, which is reported by its future
+                        let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(job));
+                    })
+                    .expect("should spawn I/O thread");
+            }
+
+            Self { sender }
+        })
+    }
+}
+
+struct ChannelState<T> {
+    items: VecDeque<T>,
+    capacity: usize,
+
+    /// The sender is gone, no more items will arrive
+    is_done: bool,
+
+    /// The receiver is gone, no more items are needed
+    is_dropped: bool,
+
+    waker: Option<Waker>,
+}
+
+struct Channel<T> {
+    state: Mutex<ChannelState<T>>,
+    not_full: Condvar,
+}
+
+impl<T> Channel<T> {
+    fn new(capacity: usize) -> Arc<Self> {
+        Arc::new(Self {
+            state: Mutex::new(ChannelState {
+                items: VecDeque::with_capacity(capacity),
+                capacity,
+                is_done: false,
+                is_dropped: false,
+                waker: None,
+            }),
+            not_full: Condvar::new(),
+        })
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ChannelState<T>> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Pushes an item, blocking while the channel is full.
+    ///
+    /// Returns `false` if the receiver is gone.
+    fn send(&self, item: T) -> bool {
+        let mut state = self.lock();
+
+        while state.items.len() >= state.capacity && !state.is_dropped {
+            state = self
+                .not_full
+                .wait(state)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+
+        if state.is_dropped {
+            return false;
+        }
+
+        state.items.push_back(item);
+        let waker = state.waker.take();
+        drop(state);
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+
+        true
+    }
+
+    fn close_sender(&self) {
+        let mut state = self.lock();
+        state.is_done = true;
+        let waker = state.waker.take();
+        drop(state);
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    fn close_receiver(&self) {
+        self.lock().is_dropped = true;
+        self.not_full.notify_all();
+    }
+
+    fn poll_recv(&self, cx: &Context<'_>) -> Poll<Option<T>> {
+        let mut state = self.lock();
+
+        if let Some(item) = state.items.pop_front() {
+            drop(state);
+            self.not_full.notify_one();
+            return Poll::Ready(Some(item));
+        }
+
+        if state.is_done {
+            return Poll::Ready(None);
+        }
+
+        state.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+/// Closes the channel for the receiver when dropped, even if the sending side panics
+struct SendGuard<T>(Arc<Channel<T>>);
+
+impl<T> Drop for SendGuard<T> {
+    fn drop(&mut self) {
+        self.0.close_sender();
+    }
+}
+
+/// Future of a blocking call that runs on a dedicated I/O thread
+///
+/// Dropping the future does not cancel the call.
+#[must_use = "the result of the call is lost unless the future is awaited"]
+pub struct IoFuture<T>(Arc<Channel<T>>);
+
+impl<T> Future for IoFuture<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        self.0.poll_recv(cx).map(|item| {
+            #[allow(clippy::expect_used)]
+            item.expect("blocking call panicked")
+        })
+    }
+}
+
+impl<T> Drop for IoFuture<T> {
+    fn drop(&mut self) {
+        self.0.close_receiver();
+    }
+}
+
+/// Stream of items of an iterator that runs on a dedicated thread
+///
+/// The thread reads a few items ahead, and stops once the stream is dropped.
+#[must_use = "streams do nothing unless polled"]
+pub struct IoStream<T>(Arc<Channel<T>>);
+
+impl<T> futures_core::Stream for IoStream<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        self.0.poll_recv(cx)
+    }
+}
+
+impl<T> Drop for IoStream<T> {
+    fn drop(&mut self) {
+        self.0.close_receiver();
+    }
+}
+
+/// Runs a blocking call on the I/O thread pool.
+pub fn spawn_blocking<T, F>(f: F) -> IoFuture<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let channel = Channel::new(1);
+    let guard = SendGuard(channel.clone());
+
+    let job = Box::new(move || {
+        guard.0.send(f());
+    });
+
+    // NOTE: The pool threads never exit, so the receiver is always alive
+    if let Err(mpsc::SendError(job)) = IoPool::get().sender.send(job) {
+        job();
+    }
+
+    IoFuture(channel)
+}
+
+/// Runs an iterator on its own thread, because a stream may be consumed slowly,
+/// which would otherwise occupy an I/O thread.
+///
+/// The iterator is created on that thread, so it does not need to be [`Send`].
+pub fn spawn_iter<I, F>(f: F) -> std::io::Result<IoStream<I::Item>>
+where
+    I: Iterator,
+    I::Item: Send + 'static,
+    F: FnOnce() -> I + Send + 'static,
+{
+    let channel = Channel::new(STREAM_BUFFER_SIZE);
+    let guard = SendGuard(channel.clone());
+
+    std::thread::Builder::new()
+        .name("io-stream".into())
+        .spawn(move || {
+            for item in f() {
+                if !guard.0.send(item) {
+                    log::trace!("io stream: exiting because stream was dropped");
+                    return;
+                }
+            }
+        })?;
+
+    Ok(IoStream(channel))
+}
@@ -2,9 +2,9 @@
 // This source code is licensed under both the Apache 2.0 and MIT License
 // (found in the LICENSE-* files in the repository)
 
-use super::reader::JournalReader;
+use super::{compression::decompress, reader::JournalReader};
 use crate::{batch::item::Item as BatchItem, journal::marker::Marker, RecoveryError};
-use lsm_tree::{coding::Encode, CompressionType, SeqNo};
+use lsm_tree::{coding::Encode, CompressionType, SeqNo, ValueType};
 use std::{fs::OpenOptions, hash::Hasher};
 
 macro_rules! fail_iter {
@@ -29,8 +29,15 @@ pub struct JournalBatchReader {
     is_in_batch: bool,
     batch_counter: u32,
     batch_seqno: SeqNo,
+    batch_compression: CompressionType,
     last_valid_pos: u64,
     checksum_builder: xxhash_rust::xxh3::Xxh3,
+
+    /// Amount of complete batches that were read
+    recovered_count: usize,
+
+    /// Amount of incomplete batches that were discarded
+    discarded_count: usize,
 }
 
 impl JournalBatchReader {
@@ -41,15 +48,30 @@ impl JournalBatchReader {
             checksum_builder: xxhash_rust::xxh3::Xxh3::new(),
             is_in_batch: false,
             batch_seqno: 0,
+            batch_compression: CompressionType::None,
             last_valid_pos: 0,
             batch_counter: 0,
+            recovered_count: 0,
+            discarded_count: 0,
         }
     }
 
+    /// Returns the amount of complete batches that were recovered so far.
+    pub fn recovered_count(&self) -> usize {
+        self.recovered_count
+    }
+
+    /// Returns the amount of incomplete batches that were discarded so far.
+    pub fn discarded_count(&self) -> usize {
+        self.discarded_count
+    }
+
     // TODO: reallocate space
     fn truncate_to(&mut self, last_valid_pos: u64) -> crate::Result<()> {
         log::trace!("Truncating journal to {last_valid_pos}");
 
+        self.discarded_count += 1;
+
         // TODO: on windows, reading file probably needs to be closed first...?
 
         let file = OpenOptions::new().write(true).open(&self.reader.path)?;
@@ -92,12 +114,6 @@ impl Iterator for JournalBatchReader {
                     seqno,
                     compression,
                 } => {
-                    // TODO: journal compression maybe in the future
-                    assert!(
-                        compression == CompressionType::None,
-                        "journal compression not supported"
-                    );
-
                     if self.is_in_batch {
                         log::debug!("Invalid batch: found batch start inside batch");
 
@@ -110,6 +126,7 @@ impl Iterator for JournalBatchReader {
                     self.is_in_batch = true;
                     self.batch_counter = item_count;
                     self.batch_seqno = seqno;
+                    self.batch_compression = compression;
                 }
                 Marker::End(expected_checksum) => {
                     if self.batch_counter > 0 {
@@ -139,8 +156,23 @@ impl Iterator for JournalBatchReader {
                     self.batch_counter = 0;
 
                     self.last_valid_pos = journal_file_pos;
+                    self.recovered_count += 1;
+
+                    let mut items = std::mem::take(&mut self.items);
+
+                    // NOTE: The checksum is built over the encoded (compressed) items,
+                    // so only decompress after the batch has been validated
+                    if self.batch_compression != CompressionType::None {
+                        for item in items
+                            .iter_mut()
+                            .filter(|item| item.value_type == ValueType::Value)
+                        {
+                            let value = fail_iter!(decompress(self.batch_compression, &item.value)
+                                .map_err(JournalRecovery));
+                            item.value = value.into();
+                        }
+                    }
 
-                    let items = std::mem::take(&mut self.items);
                     return Some(Ok(Batch {
                         seqno: self.batch_seqno,
                         items,
@@ -0,0 +1,34 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+use super::error::RecoveryError;
+use lsm_tree::CompressionType;
+
+/// Compresses a journal item value
+pub fn compress(compression: CompressionType, bytes: &[u8]) -> Vec<u8> {
+    match compression {
+        CompressionType::None => bytes.into(),
+
+        #[cfg(feature = "lz4")]
+        CompressionType::Lz4 => lz4_flex::compress_prepend_size(bytes),
+
+        #[cfg(feature = "miniz")]
+        CompressionType::Miniz(lvl) => miniz_oxide::deflate::compress_to_vec(bytes, lvl),
+    }
+}
+
+/// Decompresses a journal item value
+pub fn decompress(compression: CompressionType, bytes: &[u8]) -> Result<Vec<u8>, RecoveryError> {
+    match compression {
+        CompressionType::None => Ok(bytes.into()),
+
+        #[cfg(feature = "lz4")]
+        CompressionType::Lz4 => lz4_flex::decompress_size_prepended(bytes)
+            .map_err(|_| RecoveryError::DecompressionFailed),
+
+        #[cfg(feature = "miniz")]
+        CompressionType::Miniz(_) => miniz_oxide::inflate::decompress_to_vec(bytes)
+            .map_err(|_| RecoveryError::DecompressionFailed),
+    }
+}
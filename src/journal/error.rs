@@ -38,4 +38,7 @@ pub enum RecoveryError {
 
     /// The checksum value does not match the expected value
     ChecksumMismatch,
+
+    /// A compressed item value could not be decompressed
+    DecompressionFailed,
 }
@@ -142,7 +142,6 @@ impl JournalManager {
                 return Ok(());
             };
 
-            // TODO: unit test: check deleted partition does not prevent journal eviction
             for item in &item.watermarks {
                 // Only check partition seqno if not deleted
                 if !item
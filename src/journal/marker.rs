@@ -136,12 +136,6 @@ impl Decode for Marker {
                 let seqno = reader.read_u64::<BigEndian>()?;
                 let compression = CompressionType::decode_from(reader)?;
 
-                assert_eq!(
-                    compression,
-                    CompressionType::None,
-                    "invalid compression type"
-                );
-
                 Ok(Self::Start {
                     item_count,
                     seqno,
@@ -3,6 +3,7 @@
 // (found in the LICENSE-* files in the repository)
 
 pub mod batch_reader;
+mod compression;
 pub mod error;
 pub mod manager;
 pub mod marker;
@@ -18,6 +19,7 @@ use recovery::{recover_journals, RecoveryResult};
 use std::{
     path::{Path, PathBuf},
     sync::{Mutex, MutexGuard},
+    time::Duration,
 };
 use writer::Writer;
 
@@ -81,6 +83,17 @@ impl Journal {
         self.writer.lock().expect("lock is poisoned")
     }
 
+    /// Hands out write access for the journal.
+    ///
+    /// If a thread panicked while writing to the journal, the journal may contain
+    /// a partially written batch, so `Error::Poisoned` is returned instead of panicking.
+    pub(crate) fn try_get_writer(&self) -> crate::Result<MutexGuard<'_, Writer>> {
+        self.writer.lock().map_err(|_| {
+            log::error!("Journal writer lock is poisoned");
+            crate::Error::Poisoned
+        })
+    }
+
     pub fn path(&self) -> PathBuf {
         self.get_writer().path.clone()
     }
@@ -92,7 +105,38 @@ impl Journal {
 
     /// Persists the journal.
     pub fn persist(&self, mode: PersistMode) -> crate::Result<()> {
-        let mut lock = self.get_writer();
+        let mut lock = self.try_get_writer()?;
+        lock.persist(mode).map_err(Into::into)
+    }
+
+    /// Persists the journal up to the batch identified by the given ticket
+    /// (see [`Writer::appended_count`]).
+    ///
+    /// Writers that commit concurrently share a single sync: whoever acquires
+    /// the writer first syncs all batches appended so far, the others will find
+    /// their batch already persisted and return immediately.
+    ///
+    /// If a group commit window is given, the writer waits before syncing, so
+    /// more concurrent batches can be appended and synced together.
+    pub fn persist_batch(
+        &self,
+        ticket: u64,
+        mode: PersistMode,
+        window: Option<Duration>,
+    ) -> crate::Result<()> {
+        if mode != PersistMode::Buffer {
+            if let Some(window) = window {
+                std::thread::sleep(window);
+            }
+        }
+
+        let mut lock = self.try_get_writer()?;
+
+        if lock.is_persisted(ticket, mode) {
+            log::trace!("Journal batch {ticket} was already persisted with mode={mode:?}");
+            return Ok(());
+        }
+
         lock.persist(mode).map_err(Into::into)
     }
 
@@ -487,4 +531,190 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn journal_truncation_torn_last_batch() -> crate::Result<()> {
+        const BATCH_COUNT: u64 = 10;
+
+        let dir = tempdir()?;
+        let path = dir.path().join("0");
+
+        let batch = |seqno: u64| {
+            vec![
+                BatchItem::new("default", seqno.to_be_bytes(), *b"def", ValueType::Value),
+                BatchItem::new("default", *b"yxc", seqno.to_be_bytes(), ValueType::Value),
+            ]
+        };
+
+        {
+            let journal = Journal::create_new(&path)?;
+            let mut writer = journal.get_writer();
+
+            for seqno in 0..BATCH_COUNT {
+                let values = batch(seqno);
+                writer.write_batch(values.iter(), values.len(), seqno)?;
+            }
+        }
+
+        // Tear last batch in half
+        let file_len = {
+            let journal = Journal::from_file(&path)?;
+            let mut reader = journal.get_reader()?;
+            assert_eq!(BATCH_COUNT as usize, reader.by_ref().flatten().count());
+            assert_eq!(0, reader.discarded_count());
+
+            // NOTE: Reading truncated the preallocated space, so the file ends after the last batch
+            let file_len = std::fs::metadata(&path)?.len();
+            let file = std::fs::OpenOptions::new().write(true).open(&path)?;
+            file.set_len(file_len - 10)?;
+            file.sync_all()?;
+
+            file_len
+        };
+
+        {
+            let journal = Journal::from_file(&path)?;
+            let mut reader = journal.get_reader()?;
+
+            let collected = reader.by_ref().collect::<crate::Result<Vec<_>>>()?;
+            assert_eq!(BATCH_COUNT as usize - 1, collected.len());
+            assert_eq!(BATCH_COUNT as usize - 1, reader.recovered_count());
+            assert_eq!(1, reader.discarded_count());
+
+            for (seqno, recovered) in (0..).zip(&collected) {
+                assert_eq!(seqno, recovered.seqno);
+                assert_eq!(batch(seqno), recovered.items);
+            }
+
+            assert!(std::fs::metadata(&path)?.len() < file_len - 10);
+        }
+
+        // Journal is clean after truncation
+        {
+            let journal = Journal::from_file(&path)?;
+            let mut reader = journal.get_reader()?;
+            assert_eq!(BATCH_COUNT as usize - 1, reader.by_ref().flatten().count());
+            assert_eq!(0, reader.discarded_count());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "lz4")]
+    fn journal_compression_roundtrip() -> crate::Result<()> {
+        let dir = tempdir()?;
+        let path = dir.path().join("0");
+        let uncompressed_path = dir.path().join("1");
+
+        let value = b"abcdefgh".repeat(1_000);
+
+        let batch = vec![
+            BatchItem::new("default", *b"a", value.clone(), ValueType::Value),
+            BatchItem::new("default", *b"b", *b"", ValueType::Tombstone),
+            BatchItem::new("default", *b"c", *b"", ValueType::Value),
+        ];
+
+        let compressed_bytes = {
+            let journal = Journal::create_new(&path)?;
+            let mut writer = journal.get_writer();
+
+            writer.set_compression(lsm_tree::CompressionType::Lz4);
+            writer.write_batch(batch.iter(), batch.len(), 0)?;
+            writer.write_raw("default", b"d", &value, ValueType::Value, 1)?;
+
+            // Batches written without compression can be mixed into the same journal
+            writer.set_compression(lsm_tree::CompressionType::None);
+            writer.write_batch(batch.iter(), batch.len(), 2)?;
+
+            writer.written_bytes()
+        };
+
+        let uncompressed_bytes = {
+            let journal = Journal::create_new(&uncompressed_path)?;
+            let mut writer = journal.get_writer();
+
+            writer.write_batch(batch.iter(), batch.len(), 0)?;
+            writer.write_raw("default", b"d", &value, ValueType::Value, 1)?;
+            writer.write_batch(batch.iter(), batch.len(), 2)?;
+
+            writer.written_bytes()
+        };
+
+        assert!(compressed_bytes < uncompressed_bytes);
+
+        {
+            let journal = Journal::from_file(&path)?;
+            let reader = journal.get_reader()?;
+            let collected = reader.collect::<crate::Result<Vec<_>>>()?;
+
+            assert_eq!(3, collected.len());
+            assert_eq!(batch, collected[0].items);
+            assert_eq!(
+                vec![BatchItem::new(
+                    "default",
+                    *b"d",
+                    value.clone(),
+                    ValueType::Value
+                )],
+                collected[1].items
+            );
+            assert_eq!(batch, collected[2].items);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn journal_persist_batch_shares_sync() -> crate::Result<()> {
+        let dir = tempdir()?;
+        let path = dir.path().join("0");
+
+        let journal = Journal::create_new(&path)?;
+        let batch = [BatchItem::new("default", *b"a", *b"a", ValueType::Value)];
+
+        let tickets = (0..4)
+            .map(|seqno| {
+                let mut writer = journal.get_writer();
+                writer.write_batch(batch.iter(), batch.len(), seqno)?;
+                Ok(writer.appended_count())
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+
+        // NOTE: Simulate the writers persisting in reverse order,
+        // the first sync covers all batches appended before it
+        for ticket in tickets.iter().rev() {
+            journal.persist_batch(*ticket, PersistMode::SyncAll, None)?;
+        }
+        assert_eq!(1, journal.get_writer().sync_count());
+
+        for ticket in &tickets {
+            assert!(journal
+                .get_writer()
+                .is_persisted(*ticket, PersistMode::SyncAll));
+            assert!(journal
+                .get_writer()
+                .is_persisted(*ticket, PersistMode::SyncData));
+        }
+
+        let ticket = {
+            let mut writer = journal.get_writer();
+            writer.write_batch(batch.iter(), batch.len(), 4)?;
+            writer.appended_count()
+        };
+        assert!(!journal
+            .get_writer()
+            .is_persisted(ticket, PersistMode::SyncAll));
+
+        journal.persist_batch(ticket, PersistMode::SyncData, None)?;
+        assert_eq!(2, journal.get_writer().sync_count());
+        assert!(journal
+            .get_writer()
+            .is_persisted(ticket, PersistMode::SyncData));
+        assert!(!journal
+            .get_writer()
+            .is_persisted(ticket, PersistMode::SyncAll));
+
+        Ok(())
+    }
 }
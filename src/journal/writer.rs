@@ -2,10 +2,14 @@
 // This source code is licensed under both the Apache 2.0 and MIT License
 // (found in the LICENSE-* files in the repository)
 
-use super::marker::{serialize_marker_item, Marker};
+use super::{
+    compression::compress,
+    marker::{serialize_marker_item, Marker},
+};
 use crate::{batch::item::Item as BatchItem, file::fsync_directory, journal::recovery::JournalId};
-use lsm_tree::{coding::Encode, EncodeError, SeqNo, ValueType};
+use lsm_tree::{coding::Encode, CompressionType, EncodeError, SeqNo, ValueType};
 use std::{
+    borrow::Cow,
     fs::{rename, File, OpenOptions},
     hash::Hasher,
     io::{BufWriter, Write},
@@ -17,15 +21,61 @@ pub const PRE_ALLOCATED_BYTES: u64 = 32 * 1_024 * 1_024;
 
 pub const JOURNAL_BUFFER_BYTES: usize = 8 * 1_024;
 
+/// Tracks which appended batches have been persisted
+///
+/// Survives journal rotations, so concurrent writers can check
+/// whether their batch was already synced by another writer.
+#[derive(Copy, Clone, Debug, Default)]
+struct PersistState {
+    /// Amount of batches appended to the journal
+    appended: u64,
+
+    /// Amount of appended batches that were flushed to OS buffers
+    flushed: u64,
+
+    /// Amount of appended batches that were synced using `fdatasync`
+    synced_data: u64,
+
+    /// Amount of appended batches that were synced using `fsync`
+    synced_all: u64,
+
+    /// Amount of syncs performed
+    sync_count: u64,
+}
+
 pub struct Writer {
     pub(crate) path: PathBuf,
     file: BufWriter<File>,
     buf: Vec<u8>,
 
     is_buffer_dirty: bool,
+
+    /// Amount of bytes written since the writer was opened
+    written_bytes: u64,
+
+    /// Compression of item values of newly written batches
+    compression: CompressionType,
+
+    persist_state: PersistState,
+
+    /// If `true`, no more writes are accepted, see [`crate::Keyspace::close`]
+    pub(crate) is_closed: bool,
 }
 
 /// The persist mode allows setting the durability guarantee of previous writes
+///
+/// The sync policy of a keyspace can be chosen as follows:
+///
+/// - sync on every commit: set the durability of a write batch or transaction to
+///   [`PersistMode::SyncData`] or [`PersistMode::SyncAll`]; the commit only returns once the
+///   journal is synced, so no acknowledged write is lost on power loss
+/// - sync every N ms: use [`crate::Config::fsync_ms`]; writes of the last N ms may be lost on
+///   power loss, but all commits in that window share a single fsync
+/// - never sync: the default; data is flushed to OS buffers on every write, so it survives
+///   an application crash, but may be lost on power loss or OS crash
+///
+/// Use [`crate::Config::manual_journal_persist`] to not even flush to OS buffers automatically,
+/// and call [`crate::Keyspace::persist`] instead.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum PersistMode {
     /// Flushes data to OS buffers. This allows the OS to write out data in case of an
@@ -49,6 +99,43 @@ impl Writer {
         Ok(self.file.get_ref().metadata()?.len())
     }
 
+    /// Returns the amount of bytes written since the writer was opened.
+    ///
+    /// Unlike [`Writer::len`], this does not include the preallocated space.
+    pub fn written_bytes(&self) -> u64 {
+        self.written_bytes
+    }
+
+    /// Sets the compression used for item values of newly written batches.
+    pub fn set_compression(&mut self, compression: CompressionType) {
+        self.compression = compression;
+    }
+
+    /// Returns the amount of batches appended to the journal.
+    ///
+    /// The returned value can be used as ticket for [`Writer::is_persisted`].
+    pub fn appended_count(&self) -> u64 {
+        self.persist_state.appended
+    }
+
+    /// Returns `true` if the first `ticket` appended batches have
+    /// already been persisted with at least the given persist mode.
+    pub fn is_persisted(&self, ticket: u64, mode: PersistMode) -> bool {
+        let persisted = match mode {
+            PersistMode::Buffer => self.persist_state.flushed,
+            PersistMode::SyncData => self.persist_state.synced_data,
+            PersistMode::SyncAll => self.persist_state.synced_all,
+        };
+
+        persisted >= ticket
+    }
+
+    /// Returns the amount of syncs performed on the journal.
+    #[cfg(test)]
+    pub fn sync_count(&self) -> u64 {
+        self.persist_state.sync_count
+    }
+
     pub fn rotate(&mut self) -> crate::Result<(PathBuf, PathBuf)> {
         self.persist(PersistMode::SyncAll)?;
 
@@ -92,7 +179,13 @@ impl Writer {
         // TODO: we clone the path on every rotation...
         // TODO: we shouldn't create + assign a new writer
         // TODO: but just change ourselves accordingly
+        let compression = self.compression;
+        let persist_state = self.persist_state;
+        let is_closed = self.is_closed;
         *self = Self::create_new(&new_path)?;
+        self.compression = compression;
+        self.persist_state = persist_state;
+        self.is_closed = is_closed;
 
         // IMPORTANT: fsync folder on Unix
         fsync_directory(&folder)?;
@@ -111,6 +204,10 @@ impl Writer {
             file: BufWriter::new(file),
             buf: Vec::new(),
             is_buffer_dirty: false,
+            written_bytes: 0,
+            compression: CompressionType::None,
+            persist_state: PersistState::default(),
+            is_closed: false,
         })
     }
 
@@ -127,6 +224,10 @@ impl Writer {
                 file: BufWriter::with_capacity(JOURNAL_BUFFER_BYTES, file),
                 buf: Vec::new(),
                 is_buffer_dirty: false,
+                written_bytes: 0,
+                compression: CompressionType::None,
+                persist_state: PersistState::default(),
+                is_closed: false,
             });
         }
 
@@ -137,6 +238,10 @@ impl Writer {
             file: BufWriter::with_capacity(JOURNAL_BUFFER_BYTES, file),
             buf: Vec::new(),
             is_buffer_dirty: false,
+            written_bytes: 0,
+            compression: CompressionType::None,
+            persist_state: PersistState::default(),
+            is_closed: false,
         })
     }
 
@@ -149,10 +254,35 @@ impl Writer {
             self.is_buffer_dirty = false;
         }
 
+        let state = &mut self.persist_state;
+        state.flushed = state.appended;
+
         match mode {
-            PersistMode::SyncAll => self.file.get_mut().sync_all(),
-            PersistMode::SyncData => self.file.get_mut().sync_data(),
-            PersistMode::Buffer => Ok(()),
+            PersistMode::SyncAll => {
+                self.file.get_mut().sync_all()?;
+                state.synced_all = state.appended;
+                state.synced_data = state.appended;
+                state.sync_count += 1;
+            }
+            PersistMode::SyncData => {
+                self.file.get_mut().sync_data()?;
+                state.synced_data = state.appended;
+                state.sync_count += 1;
+            }
+            PersistMode::Buffer => {}
+        }
+
+        Ok(())
+    }
+
+    /// Compresses the value of an item, if compression is enabled
+    ///
+    /// Tombstones do not carry a value, so they are never compressed.
+    fn compress_value<'a>(&self, value: &'a [u8], value_type: ValueType) -> Cow<'a, [u8]> {
+        if self.compression == CompressionType::None || value_type != ValueType::Value {
+            Cow::Borrowed(value)
+        } else {
+            Cow::Owned(compress(self.compression, value))
         }
     }
 
@@ -163,7 +293,7 @@ impl Writer {
         Marker::Start {
             item_count,
             seqno,
-            compression: lsm_tree::CompressionType::None,
+            compression: self.compression,
         }
         .encode_into(&mut self.buf)?;
 
@@ -200,7 +330,8 @@ impl Writer {
         byte_count += self.write_start(1, seqno)?;
         self.buf.clear();
 
-        serialize_marker_item(&mut self.buf, partition, key, value, value_type)?;
+        let value = self.compress_value(value, value_type);
+        serialize_marker_item(&mut self.buf, partition, key, &value, value_type)?;
 
         self.file.write_all(&self.buf)?;
 
@@ -211,6 +342,9 @@ impl Writer {
         let checksum = hasher.finish();
         byte_count += self.write_end(checksum)?;
 
+        self.written_bytes += byte_count as u64;
+        self.persist_state.appended += 1;
+
         Ok(byte_count)
     }
 
@@ -241,11 +375,13 @@ impl Writer {
         for item in items {
             debug_assert!(self.buf.is_empty());
 
+            let value = self.compress_value(&item.value, item.value_type);
+
             serialize_marker_item(
                 &mut self.buf,
                 &item.partition,
                 &item.key,
-                &item.value,
+                &value,
                 item.value_type,
             )?;
 
@@ -260,6 +396,9 @@ impl Writer {
         let checksum = hasher.finish();
         byte_count += self.write_end(checksum)?;
 
+        self.written_bytes += byte_count as u64;
+        self.persist_state.appended += 1;
+
         Ok(byte_count)
     }
 }
@@ -11,21 +11,27 @@ use crate::{
     },
     flush::manager::FlushManager,
     journal::{manager::JournalManager, writer::PersistMode, Journal},
+    lock_file::LockFile,
     monitor::Monitor,
-    partition::name::is_valid_partition_name,
+    partition::{
+        indexed::{IndexedPartition, INDEX_PARTITION_SUFFIX},
+        name::is_valid_partition_name,
+    },
     recovery::{recover_partitions, recover_sealed_memtables},
+    snapshot_nonce::SnapshotNonce,
     snapshot_tracker::SnapshotTracker,
     version::Version,
     write_buffer_manager::WriteBufferManager,
     HashMap, PartitionCreateOptions, PartitionHandle,
 };
-use lsm_tree::{AbstractTree, SequenceNumberCounter};
+use lsm_tree::{AbstractTree, KvPair, SequenceNumberCounter};
 use std::{
     fs::{remove_dir_all, File},
+    ops::RangeBounds,
     path::Path,
     sync::{
         atomic::{AtomicBool, AtomicUsize},
-        Arc, RwLock,
+        Arc, Mutex, RwLock,
     },
 };
 use std_semaphore::Semaphore;
@@ -77,12 +83,14 @@ pub struct KeyspaceInner {
 
     #[doc(hidden)]
     pub snapshot_tracker: SnapshotTracker,
-}
 
-impl Drop for KeyspaceInner {
-    fn drop(&mut self) {
-        log::trace!("Dropping Keyspace");
+    /// Exclusive lock on the keyspace folder, released when closing or dropping
+    lock_file: Mutex<Option<LockFile>>,
+}
 
+impl KeyspaceInner {
+    /// Signals all background threads to stop, and waits for them to exit.
+    fn stop_background_threads(&self) {
         self.stop_signal.send();
 
         while self
@@ -96,6 +104,14 @@ impl Drop for KeyspaceInner {
             self.flush_semaphore.release();
             self.compaction_manager.notify_empty();
         }
+    }
+}
+
+impl Drop for KeyspaceInner {
+    fn drop(&mut self) {
+        log::trace!("Dropping Keyspace");
+
+        self.stop_background_threads();
 
         self.config.descriptor_table.clear();
 
@@ -295,6 +311,85 @@ impl Keyspace {
         Ok(())
     }
 
+    /// Flushes all memtables into segments and shuts the keyspace down.
+    ///
+    /// Once closed, writes through any handle of the keyspace return [`crate::Error::KeyspaceClosed`].
+    /// Background threads are stopped, and the keyspace folder is unlocked, so it can
+    /// be opened again right away. Because all data is in segments, opening the keyspace
+    /// again does not need to replay the journal.
+    ///
+    /// Writes that are still in progress while closing may only be persisted in the journal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fjall::{Config, PartitionCreateOptions};
+    /// # let folder = tempfile::tempdir()?;
+    /// let keyspace = Config::new(&folder).open()?;
+    /// let items = keyspace.open_partition("my_items", PartitionCreateOptions::default())?;
+    ///
+    /// items.insert("a", "hello")?;
+    ///
+    /// keyspace.close()?;
+    /// assert!(matches!(items.insert("b", "hello"), Err(fjall::Error::KeyspaceClosed)));
+    /// #
+    /// # Ok::<_, fjall::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns error, if a memtable could not be flushed, or an IO error occurred.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a lock is poisoned.
+    pub fn close(self) -> crate::Result<()> {
+        log::info!("Closing keyspace at {}", self.config.path.display());
+
+        self.journal.try_get_writer()?.is_closed = true;
+
+        self.stop_background_threads();
+
+        let partitions = self
+            .partitions
+            .read()
+            .expect("lock is poisoned")
+            .values()
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for partition in &partitions {
+            partition.rotate_memtable()?;
+        }
+
+        // NOTE: Flush workers are stopped, so the sealed memtables are flushed in this thread
+        let parallelism = self.config.flush_workers_count.max(1);
+
+        while !self
+            .flush_manager
+            .read()
+            .expect("lock is poisoned")
+            .is_empty()
+        {
+            crate::flush::worker::run(
+                &self.flush_manager,
+                &self.journal_manager,
+                &self.compaction_manager,
+                &self.write_buffer_manager,
+                &self.snapshot_tracker,
+                parallelism,
+            )?;
+        }
+
+        self.journal.persist(PersistMode::SyncAll)?;
+
+        self.lock_file.lock().expect("lock is poisoned").take();
+
+        log::info!("Closed keyspace at {}", self.config.path.display());
+
+        Ok(())
+    }
+
     /// Opens a keyspace in the given directory.
     ///
     /// # Errors
@@ -410,7 +505,8 @@ impl Keyspace {
     ///
     /// # Errors
     ///
-    /// Returns error, if an IO error occurred.
+    /// Returns error, if an IO error occurred, or if the partition has a reverse index,
+    /// see [`Keyspace::open_indexed_partition`].
     ///
     /// # Panics
     ///
@@ -419,11 +515,25 @@ impl Keyspace {
         &self,
         name: &str,
         create_options: PartitionCreateOptions,
+    ) -> crate::Result<PartitionHandle> {
+        self.open_partition_inner(name, create_options, false)
+    }
+
+    fn open_partition_inner(
+        &self,
+        name: &str,
+        create_options: PartitionCreateOptions,
+        is_indexed: bool,
     ) -> crate::Result<PartitionHandle> {
         assert!(is_valid_partition_name(name));
 
         let mut partitions = self.partitions.write().expect("lock is poisoned");
 
+        // NOTE: Writes through a plain handle would not update the reverse index
+        if !is_indexed && partitions.contains_key(&*format!("{name}{INDEX_PARTITION_SUFFIX}")) {
+            return Err(crate::Error::PartitionIndexed);
+        }
+
         Ok(if let Some(partition) = partitions.get(name) {
             partition.clone()
         } else {
@@ -439,6 +549,69 @@ impl Keyspace {
         })
     }
 
+    /// Creates or opens a partition that automatically maintains a reverse index.
+    ///
+    /// For every key-value pair, `index_fn` returns the index keys the pair should be
+    /// found under. Index entries are stored in a sibling partition called `{name}#idx`,
+    /// and are written in the same atomic batch as the primary data.
+    ///
+    /// Once the index exists, the partition can no longer be opened using
+    /// [`Keyspace::open_partition`], because writes through a plain handle
+    /// would not update the index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fjall::{Config, PartitionCreateOptions};
+    /// #
+    /// # let folder = tempfile::tempdir()?;
+    /// # let keyspace = Config::new(folder).open()?;
+    /// // Index users by their city
+    /// let users = keyspace.open_indexed_partition(
+    ///     "users",
+    ///     PartitionCreateOptions::default(),
+    ///     |_, value| vec![value.to_vec()],
+    /// )?;
+    ///
+    /// users.insert("alice", "berlin")?;
+    /// users.insert("bob", "berlin")?;
+    /// users.insert("carol", "paris")?;
+    ///
+    /// assert_eq!(2, users.lookup_by_index("berlin")?.len());
+    /// #
+    /// # Ok::<(), fjall::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns error, if an IO error occurred.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the partition name is invalid.
+    pub fn open_indexed_partition(
+        &self,
+        name: &str,
+        create_options: PartitionCreateOptions,
+        index_fn: impl Fn(&[u8], &[u8]) -> Vec<Vec<u8>> + Send + Sync + 'static,
+    ) -> crate::Result<IndexedPartition> {
+        assert!(is_valid_partition_name(name));
+
+        let index_name = format!("{name}{INDEX_PARTITION_SUFFIX}");
+
+        // IMPORTANT: Create the index first, so the primary partition
+        // can not be opened as a plain partition in the meantime
+        let index = self.open_partition_inner(&index_name, create_options.clone(), true)?;
+        let primary = self.open_partition_inner(name, create_options, true)?;
+
+        Ok(IndexedPartition::new(
+            self.clone(),
+            primary,
+            index,
+            Arc::new(index_fn),
+        ))
+    }
+
     /// Returns the amount of partitions
     #[must_use]
     pub fn partition_count(&self) -> usize {
@@ -446,6 +619,30 @@ impl Keyspace {
     }
 
     /// Gets a list of all partition names in the keyspace
+    ///
+    /// When reopening a keyspace, all partitions found on disk are recovered,
+    /// so they are listed without needing to know their names in advance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fjall::{Config, Keyspace, PartitionCreateOptions};
+    /// #
+    /// # let folder = tempfile::tempdir()?;
+    /// # let keyspace = Config::new(&folder).open()?;
+    /// keyspace.open_partition("default", PartitionCreateOptions::default())?;
+    /// # drop(keyspace);
+    ///
+    /// let keyspace = Config::new(&folder).open()?;
+    /// assert_eq!(1, keyspace.list_partitions().len());
+    ///
+    /// // Partitions can then be opened by name
+    /// for name in keyspace.list_partitions() {
+    ///     let partition = keyspace.open_partition(&name, PartitionCreateOptions::default())?;
+    /// }
+    /// #
+    /// # Ok::<(), fjall::Error>(())
+    /// ```
     #[must_use]
     pub fn list_partitions(&self) -> Vec<PartitionKey> {
         self.partitions
@@ -522,6 +719,120 @@ impl Keyspace {
         self.seqno.get()
     }
 
+    /// Forces a snapshot GC pass and schedules compactions for partitions
+    /// that contain reclaimable data below the new GC watermark.
+    ///
+    /// Normally, the GC watermark advances opportunistically as snapshots are closed.
+    /// After closing a long-lived snapshot, this can be used to reclaim space right away.
+    ///
+    /// Returns the sequence number up to which old versions are safe to be dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fjall::{Config, PartitionCreateOptions};
+    /// #
+    /// # let folder = tempfile::tempdir()?;
+    /// # let keyspace = Config::new(folder).open()?;
+    /// # let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+    /// let snapshot = partition.snapshot();
+    ///
+    /// for _ in 0..100 {
+    ///     partition.insert("a", "abc")?;
+    /// }
+    ///
+    /// drop(snapshot);
+    /// assert!(keyspace.gc_now() > 0);
+    /// #
+    /// # Ok::<(), fjall::Error>(())
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lock is poisoned.
+    #[must_use]
+    pub fn gc_now(&self) -> crate::Instant {
+        let watermark = self.snapshot_tracker.gc_now(self.seqno.get());
+
+        log::debug!("gc_now: GC watermark is now {watermark}");
+
+        let partitions = self
+            .partitions
+            .read()
+            .expect("lock is poisoned")
+            .values()
+            .filter(|partition| partition.has_reclaimable_data(watermark))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for partition in partitions {
+            log::debug!("gc_now: scheduling compaction for {:?}", partition.name);
+            self.compaction_manager.notify(partition);
+        }
+
+        watermark
+    }
+
+    /// Returns iterators over a range of items of multiple partitions,
+    /// all pinned to the same snapshot instant.
+    ///
+    /// The iterators share a single snapshot, so they observe a consistent
+    /// cross-partition view, even if the partitions are written to concurrently.
+    ///
+    /// The iterators are returned in the same order as the given partitions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fjall::{Config, PartitionCreateOptions};
+    /// #
+    /// # let folder = tempfile::tempdir()?;
+    /// # let keyspace = Config::new(folder).open()?;
+    /// let orders = keyspace.open_partition("orders", PartitionCreateOptions::default())?;
+    /// let invoices = keyspace.open_partition("invoices", PartitionCreateOptions::default())?;
+    ///
+    /// orders.insert("1", "abc")?;
+    /// invoices.insert("1", "def")?;
+    ///
+    /// let mut iters = keyspace.range_across(&[&orders, &invoices], "1"..="9");
+    ///
+    /// orders.insert("2", "abc")?;
+    /// invoices.insert("2", "def")?;
+    ///
+    /// for iter in &mut iters {
+    ///     assert_eq!(1, iter.count());
+    /// }
+    /// #
+    /// # Ok::<(), fjall::Error>(())
+    /// ```
+    #[must_use]
+    pub fn range_across<K: AsRef<[u8]>, R: RangeBounds<K> + Clone>(
+        &self,
+        partitions: &[&PartitionHandle],
+        range: R,
+    ) -> Vec<impl DoubleEndedIterator<Item = crate::Result<KvPair>> + 'static> {
+        let seqno = self.instant();
+
+        // NOTE: Register a single snapshot for all partitions; it is
+        // freed when the last iterator is dropped
+        let nonce = Arc::new(SnapshotNonce::new(seqno, self.snapshot_tracker.clone()));
+
+        partitions
+            .iter()
+            .map(|partition| {
+                let nonce = nonce.clone();
+
+                partition
+                    .tree
+                    .range_with_seqno(range.clone(), seqno, None)
+                    .map(move |item| {
+                        let _nonce = &nonce;
+                        item.map_err(Into::into)
+                    })
+            })
+            .collect()
+    }
+
     fn check_version<P: AsRef<Path>>(path: P) -> crate::Result<()> {
         let bytes = std::fs::read(path.as_ref().join(FJALL_MARKER))?;
 
@@ -545,6 +856,8 @@ impl Keyspace {
         // TODO:
         // let recovery_mode = config.journal_recovery_mode;
 
+        let lock_file = LockFile::acquire(&config.path)?;
+
         // Check version
         Self::check_version(&config.path)?;
 
@@ -554,10 +867,16 @@ impl Keyspace {
         log::debug!("journal recovery result: {journal_recovery:#?}");
 
         let active_journal = Arc::new(journal_recovery.active);
+        active_journal
+            .get_writer()
+            .set_compression(config.journal_compression);
         let sealed_journals = journal_recovery.sealed;
 
         let journal_manager = JournalManager::from_active(active_journal.path());
 
+        let snapshot_tracker =
+            SnapshotTracker::new(config.snapshot_safety_gap, config.max_open_snapshots);
+
         // Construct (empty) keyspace, then fill back with partition data
         let inner = KeyspaceInner {
             config,
@@ -575,7 +894,8 @@ impl Keyspace {
             active_background_threads: Arc::default(),
             write_buffer_manager: WriteBufferManager::default(),
             is_poisoned: Arc::default(),
-            snapshot_tracker: SnapshotTracker::default(),
+            snapshot_tracker,
+            lock_file: Mutex::new(Some(lock_file)),
         };
 
         let keyspace = Self(Arc::new(inner));
@@ -610,9 +930,9 @@ impl Keyspace {
             if !journal_recovery.was_active_created {
                 log::trace!("Recovering active memtables from active journal");
 
-                let reader = keyspace.journal.get_reader()?;
+                let mut reader = keyspace.journal.get_reader()?;
 
-                for batch in reader {
+                for batch in &mut reader {
                     let batch = batch?;
 
                     for item in batch.items {
@@ -634,6 +954,12 @@ impl Keyspace {
                     }
                 }
 
+                log::debug!(
+                    "Recovered {} batches from active journal, discarded {} incomplete batches",
+                    reader.recovered_count(),
+                    reader.discarded_count(),
+                );
+
                 for partition in partitions.values() {
                     let size = partition.tree.active_memtable_size().into();
 
@@ -671,6 +997,8 @@ impl Keyspace {
 
         std::fs::create_dir_all(&path)?;
 
+        let lock_file = LockFile::acquire(&path)?;
+
         let marker_path = path.join(FJALL_MARKER);
         assert!(!marker_path.try_exists()?);
 
@@ -682,8 +1010,14 @@ impl Keyspace {
 
         let active_journal_path = journal_folder_path.join("0");
         let journal = Journal::create_new(&active_journal_path)?;
+        journal
+            .get_writer()
+            .set_compression(config.journal_compression);
         let journal = Arc::new(journal);
 
+        let snapshot_tracker =
+            SnapshotTracker::new(config.snapshot_safety_gap, config.max_open_snapshots);
+
         let inner = KeyspaceInner {
             config,
             journal,
@@ -702,7 +1036,8 @@ impl Keyspace {
             active_background_threads: Arc::default(),
             write_buffer_manager: WriteBufferManager::default(),
             is_poisoned: Arc::default(),
-            snapshot_tracker: SnapshotTracker::default(),
+            snapshot_tracker,
+            lock_file: Mutex::new(Some(lock_file)),
         };
 
         // NOTE: Lastly, fsync .fjall marker, which contains the version
@@ -809,7 +1144,8 @@ impl Keyspace {
     pub fn force_flush(&self) {
         let parallelism = self.config.flush_workers_count;
 
-        crate::flush::worker::run(
+        // NOTE: Errors are logged by the flush worker
+        let _ = crate::flush::worker::run(
             &self.flush_manager,
             &self.journal_manager,
             &self.compaction_manager,
@@ -841,7 +1177,8 @@ impl Keyspace {
                     log::trace!("flush worker: acquiring flush semaphore");
                     flush_semaphore.acquire();
 
-                    crate::flush::worker::run(
+                    // NOTE: Errors are logged by the flush worker
+                    let _ = crate::flush::worker::run(
                         &flush_manager,
                         &journal_manager,
                         &compaction_manager,
@@ -884,6 +1221,196 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    pub fn write_stall_mode_error_rejects_writes() -> crate::Result<()> {
+        let folder = tempfile::tempdir()?;
+        let config = Config::new(&folder)
+            .max_write_buffer_size(1_024 * 1_024)
+            .write_stall_mode(crate::WriteStallMode::Error);
+        let keyspace = Keyspace::create_or_recover(config)?;
+        let db = keyspace.open_partition("default", Default::default())?;
+
+        let mut written = 0;
+
+        let result = loop {
+            if let Err(e) = db.insert(written.to_string(), [0; 64 * 1_024]) {
+                break e;
+            }
+
+            written += 1;
+            assert!(written < 100, "write buffer should saturate");
+        };
+        assert!(matches!(result, crate::Error::WriteBufferFull));
+        assert!(keyspace.write_buffer_size() >= 1_024 * 1_024);
+
+        // NOTE: Rejected writes are not applied
+        assert_eq!(written, db.len()?);
+
+        let mut batch = keyspace.batch();
+        batch.insert(&db, "a", "a");
+        assert!(matches!(batch.commit(), Err(crate::Error::WriteBufferFull)));
+
+        db.rotate_memtable()?;
+        keyspace.force_flush();
+        assert_eq!(0, keyspace.write_buffer_size());
+
+        db.insert("a", "a")?;
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn write_stall_mode_block_stalls_writes() -> crate::Result<()> {
+        use std::sync::atomic::Ordering;
+
+        let folder = tempfile::tempdir()?;
+        let config = Config::new(&folder).max_write_buffer_size(1_024 * 1_024);
+        let keyspace = Keyspace::create_or_recover(config)?;
+        let db = keyspace.open_partition("default", Default::default())?;
+
+        let done = Arc::new(AtomicBool::new(false));
+
+        let writer = std::thread::spawn({
+            let db = db.clone();
+            let done = done.clone();
+
+            move || -> crate::Result<()> {
+                for idx in 0..20 {
+                    db.insert(idx.to_string(), [0; 64 * 1_024])?;
+                }
+                done.store(true, Ordering::Release);
+                Ok(())
+            }
+        });
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+
+        while keyspace.write_buffer_size() <= 1_024 * 1_024 {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "write buffer should become saturated"
+            );
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+
+        // NOTE: Nothing is flushing, so the writer is stuck once the write buffer is full
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        assert!(!done.load(Ordering::Acquire));
+
+        db.rotate_memtable()?;
+        keyspace.force_flush();
+
+        writer.join().expect("should join")?;
+        assert!(done.load(Ordering::Acquire));
+        assert_eq!(20, db.len()?);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn gc_now_advances_watermark() -> crate::Result<()> {
+        let folder = tempfile::tempdir()?;
+        let config = Config::new(&folder);
+        let keyspace = Keyspace::create_or_recover(config)?;
+        let db = keyspace.open_partition("default", Default::default())?;
+        let db2 = keyspace.open_partition("default2", Default::default())?;
+
+        db.insert("a", "a")?;
+        db.rotate_memtable()?;
+        keyspace.force_flush();
+
+        db2.insert("a", "a")?;
+        db2.rotate_memtable()?;
+        keyspace.force_flush();
+
+        let instant = keyspace.instant();
+        let snapshot = db.snapshot_at(instant);
+
+        db.remove("a")?;
+        db.rotate_memtable()?;
+        keyspace.force_flush();
+
+        for _ in 0..100 {
+            db2.insert("b", "b")?;
+        }
+
+        let queued = keyspace.compaction_manager.len();
+        assert_eq!(3, queued);
+
+        // NOTE: The open snapshot holds back the watermark
+        let watermark = keyspace.gc_now();
+        assert!(watermark < instant);
+        assert_eq!(queued, keyspace.compaction_manager.len());
+
+        drop(snapshot);
+
+        let watermark = keyspace.gc_now();
+        assert!(watermark > instant);
+        assert_eq!(watermark, keyspace.snapshot_tracker.get_seqno_safe_to_gc());
+
+        // NOTE: Only the partition with the tombstone has reclaimable data
+        assert_eq!(queued + 1, keyspace.compaction_manager.len());
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn poisoned_journal_lock_does_not_panic() -> crate::Result<()> {
+        let folder = tempfile::tempdir()?;
+        let keyspace = Config::new(&folder).open()?;
+        let db = keyspace.open_partition("default", Default::default())?;
+
+        db.insert("a", "a")?;
+
+        let journal = keyspace.journal.clone();
+        let result = std::thread::spawn(move || {
+            let _writer = journal.get_writer();
+            panic!("oops");
+        })
+        .join();
+        assert!(result.is_err());
+
+        assert!(matches!(db.insert("b", "b"), Err(crate::Error::Poisoned)));
+        assert!(matches!(db.remove("a"), Err(crate::Error::Poisoned)));
+        assert!(matches!(
+            keyspace.persist(PersistMode::Buffer),
+            Err(crate::Error::Poisoned)
+        ));
+
+        assert_eq!(Some("a".as_bytes().into()), db.get("a")?);
+        assert!(!db.contains_key("b")?);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn poisoned_snapshot_tracker_lock_does_not_panic() -> crate::Result<()> {
+        let folder = tempfile::tempdir()?;
+        let keyspace = Config::new(&folder).open()?;
+        let db = keyspace.open_partition("default", Default::default())?;
+
+        db.insert("a", "a")?;
+
+        let snapshot_tracker = keyspace.snapshot_tracker.clone();
+        let result = std::thread::spawn(move || {
+            let _lock = snapshot_tracker
+                .lowest_freed_instant
+                .write()
+                .expect("lock is poisoned");
+            panic!("oops");
+        })
+        .join();
+        assert!(result.is_err());
+
+        for _ in 0..100 {
+            let snapshot = db.snapshot();
+            assert_eq!(Some("a".as_bytes().into()), snapshot.get("a")?);
+        }
+        assert_eq!(0, keyspace.snapshot_tracker.get_seqno_safe_to_gc());
+
+        Ok(())
+    }
+
     #[test]
     pub fn recover_after_rotation_multiple_partitions() -> crate::Result<()> {
         let folder = tempfile::tempdir()?;
@@ -991,6 +1518,258 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    pub fn force_flush_bounded_by_flush_workers() -> crate::Result<()> {
+        let folder = tempfile::tempdir()?;
+
+        let config = Config::new(folder).flush_workers(2);
+        let keyspace = Keyspace::create_or_recover(config)?;
+
+        let partitions = ["a", "b", "c"]
+            .into_iter()
+            .map(|name| keyspace.open_partition(name, Default::default()))
+            .collect::<crate::Result<Vec<_>>>()?;
+
+        for partition in &partitions {
+            for _ in 0..100 {
+                partition.insert(nanoid::nanoid!(), "abc")?;
+            }
+            partition.rotate_memtable()?;
+        }
+
+        let queued_size = {
+            let mut flush_manager = keyspace.flush_manager.write().expect("lock is poisoned");
+            assert_eq!(3, flush_manager.len());
+
+            let queued_size = flush_manager.queued_size();
+
+            // NOTE: Collected tasks stay queued (and accounted for) until they are flushed
+            let tasks = flush_manager.collect_tasks(2);
+            assert_eq!(2, tasks.values().map(Vec::len).sum::<usize>());
+            assert_eq!(3, flush_manager.len());
+            assert_eq!(queued_size, flush_manager.queued_size());
+
+            queued_size
+        };
+
+        keyspace.force_flush();
+
+        {
+            let flush_manager = keyspace.flush_manager.read().expect("lock is poisoned");
+            assert_eq!(1, flush_manager.len());
+            assert!(flush_manager.queued_size() < queued_size);
+        }
+        assert_eq!(
+            2,
+            partitions
+                .iter()
+                .map(PartitionHandle::segment_count)
+                .sum::<usize>()
+        );
+
+        keyspace.force_flush();
+
+        assert!(keyspace
+            .flush_manager
+            .read()
+            .expect("lock is poisoned")
+            .is_empty());
+        assert_eq!(
+            3,
+            partitions
+                .iter()
+                .map(PartitionHandle::segment_count)
+                .sum::<usize>()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn deleted_partition_does_not_prevent_journal_eviction() -> crate::Result<()> {
+        let folder = tempfile::tempdir()?;
+
+        let config = Config::new(folder);
+        let keyspace = Keyspace::create_or_recover(config)?;
+        let db = keyspace.open_partition("default", Default::default())?;
+        let ephemeral = keyspace.open_partition("ephemeral", Default::default())?;
+        let ephemeral_path = ephemeral.path().to_path_buf();
+
+        for _ in 0..100 {
+            db.insert(nanoid::nanoid!(), "abc")?;
+            ephemeral.insert(nanoid::nanoid!(), "abc")?;
+        }
+
+        // NOTE: Sealed journal contains unflushed data of both partitions
+        db.rotate_memtable()?;
+        ephemeral.rotate_memtable()?;
+        assert_eq!(3, keyspace.journal_count());
+
+        keyspace.delete_partition(ephemeral)?;
+        assert!(!keyspace.partition_exists("ephemeral"));
+
+        keyspace.force_flush();
+
+        // NOTE: Once the sealed journal is evicted, the last
+        // reference to the partition is dropped, and its folder is deleted
+        assert_eq!(1, keyspace.journal_count());
+        assert!(!ephemeral_path.try_exists()?);
+        assert_eq!(100, db.len()?);
+
+        let ephemeral = keyspace.open_partition("ephemeral", Default::default())?;
+        assert!(ephemeral.is_empty()?);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn partition_max_memtable_size_rotates_independently() -> crate::Result<()> {
+        let folder = tempfile::tempdir()?;
+
+        let config = Config::new(folder).max_write_buffer_size(u64::MAX);
+        let keyspace = Keyspace::create_or_recover(config)?;
+
+        let big = keyspace.open_partition(
+            "big",
+            PartitionCreateOptions::default().max_memtable_size(64_000),
+        )?;
+        let small = keyspace.open_partition("small", Default::default())?;
+
+        for x in 0..1_000u64 {
+            big.insert(x.to_be_bytes(), "a".repeat(1_000))?;
+            small.insert(x.to_be_bytes(), "a")?;
+        }
+
+        // NOTE: The big partition exceeded its own quota multiple times,
+        // while the small one is still way below its quota
+        assert!(big.tree.sealed_memtable_count() > 1);
+        assert!(big.tree.active_memtable_size() <= 64_000 + 2_000);
+        assert_eq!(0, small.tree.sealed_memtable_count());
+
+        let flush_manager = keyspace.flush_manager.read().expect("lock is poisoned");
+        assert_eq!(
+            big.tree.sealed_memtable_count(),
+            flush_manager.sealed_memtables("big").len()
+        );
+        assert!(flush_manager.sealed_memtables("small").is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn journal_rotation_size() -> crate::Result<()> {
+        let folder = tempfile::tempdir()?;
+
+        let sealed_journal_count = |keyspace: &Keyspace| -> crate::Result<usize> {
+            let mut count = 0;
+
+            for dirent in std::fs::read_dir(keyspace.config.path.join(JOURNALS_FOLDER))? {
+                let dirent = dirent?;
+
+                if dirent.file_name().to_string_lossy().ends_with(".sealed") {
+                    count += 1;
+                }
+            }
+
+            Ok(count)
+        };
+
+        {
+            let config = Config::new(&folder).journal_rotation_size(Some(10_000));
+            let keyspace = Keyspace::create_or_recover(config)?;
+            let db = keyspace.open_partition("default", Default::default())?;
+            let db2 = keyspace.open_partition("default2", Default::default())?;
+
+            for x in 0..25u64 {
+                db.insert(x.to_be_bytes(), "a".repeat(500))?;
+
+                let mut batch = keyspace.batch();
+                batch.insert(&db2, x.to_be_bytes(), "a".repeat(500));
+                batch.commit()?;
+            }
+
+            // NOTE: Journals were rotated without rotating any memtable
+            assert_eq!(3, keyspace.journal_count());
+            assert_eq!(2, sealed_journal_count(&keyspace)?);
+            assert_eq!(0, db.tree.sealed_memtable_count());
+            assert_eq!(0, db2.tree.sealed_memtable_count());
+        }
+
+        {
+            let config = Config::new(&folder)
+                .journal_rotation_size(Some(10_000))
+                .flush_workers(16);
+            let keyspace = Keyspace::create_or_recover(config)?;
+            let db = keyspace.open_partition("default", Default::default())?;
+            let db2 = keyspace.open_partition("default2", Default::default())?;
+
+            assert_eq!(25, db.len()?);
+            assert_eq!(25, db2.len()?);
+            assert_eq!(3, keyspace.journal_count());
+
+            db.rotate_memtable()?;
+            db2.rotate_memtable()?;
+            keyspace.force_flush();
+
+            // NOTE: All data is flushed, so all sealed journals can be evicted
+            assert_eq!(25, db.len()?);
+            assert_eq!(25, db2.len()?);
+            assert_eq!(1, keyspace.journal_count());
+            assert_eq!(0, sealed_journal_count(&keyspace)?);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn group_commit_shares_syncs() -> crate::Result<()> {
+        const WRITER_COUNT: usize = 32;
+
+        let folder = tempfile::tempdir()?;
+
+        let config = Config::new(&folder).group_commit_window_us(Some(20_000));
+        let keyspace = Keyspace::create_or_recover(config)?;
+        let db = keyspace.open_partition("default", Default::default())?;
+
+        let barrier = std::sync::Barrier::new(WRITER_COUNT);
+
+        std::thread::scope(|s| {
+            let handles = (0..WRITER_COUNT)
+                .map(|idx| {
+                    let keyspace = &keyspace;
+                    let db = &db;
+                    let barrier = &barrier;
+
+                    s.spawn(move || {
+                        barrier.wait();
+
+                        let mut batch = keyspace.batch().durability(Some(PersistMode::SyncAll));
+                        batch.insert(db, (idx as u64).to_be_bytes(), "abc");
+                        batch.commit()
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            for handle in handles {
+                handle.join().expect("should join")?;
+            }
+
+            Ok::<_, crate::Error>(())
+        })?;
+
+        assert_eq!(WRITER_COUNT, db.len()?);
+
+        let sync_count = keyspace.journal.get_writer().sync_count();
+        log::info!("{WRITER_COUNT} durable commits incurred {sync_count} syncs");
+        assert!(sync_count > 0);
+
+        // NOTE: How many commits share a sync depends on thread scheduling,
+        // but no commit may sync more than once, see `journal_persist_batch_shares_sync`
+        assert!(sync_count <= WRITER_COUNT as u64);
+
+        Ok(())
+    }
+
     #[test]
     pub fn force_flush_multiple_partitions() -> crate::Result<()> {
         let folder = tempfile::tempdir()?;
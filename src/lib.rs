@@ -93,12 +93,17 @@ mod config;
 pub mod drop;
 
 mod error;
+mod event;
 mod file;
 mod flush;
 mod gc;
+#[cfg(feature = "async")]
+mod io_pool;
+
 mod iter;
 mod journal;
 mod keyspace;
+mod lock_file;
 mod monitor;
 mod partition;
 mod path;
@@ -118,13 +123,22 @@ pub(crate) type HashSet<K> = std::collections::HashSet<K, xxhash_rust::xxh3::Xxh
 
 pub use {
     batch::Batch,
-    config::Config,
+    config::{Config, WriteStallMode},
     error::{Error, Result},
+    event::Event,
     gc::GarbageCollection,
     journal::{error::RecoveryError, writer::PersistMode},
     keyspace::Keyspace,
     partition::{
-        options::CreateOptions as PartitionCreateOptions, options::KvSeparationOptions,
+        change::Change,
+        disk_usage::DiskUsage,
+        indexed::IndexedPartition,
+        key_version::KeyVersion,
+        level_summary::LevelSummary,
+        options::CreateOptions as PartitionCreateOptions,
+        options::KvSeparationOptions,
+        segment_summary::SegmentSummary,
+        verify::{VerifyIssue, VerifyReport},
         PartitionHandle,
     },
     tracked_snapshot::TrackedSnapshot as Snapshot,
@@ -136,9 +150,16 @@ pub use tx::{
     keyspace::{TransactionalKeyspace, TxKeyspace},
     partition::TransactionalPartitionHandle,
     read_tx::ReadTransaction,
+    write::Savepoint,
     write_tx::WriteTransaction,
 };
 
+#[cfg(feature = "ssi_tx")]
+pub use tx::write::ssi::{Conflict, ConflictDetails};
+
+#[cfg(feature = "async")]
+pub use io_pool::{IoFuture, IoStream};
+
 /// Alias for [`Batch`]
 pub type WriteBatch = Batch;
 
@@ -170,4 +191,5 @@ pub use lsm_tree::AbstractTree;
 
 pub use lsm_tree::{
     AnyTree, BlobCache, BlockCache, CompressionType, KvPair, Slice, TreeType, UserKey, UserValue,
+    ValueType,
 };
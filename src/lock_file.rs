@@ -0,0 +1,83 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+use crate::file::LOCK_FILE;
+use std::{fs::File, path::Path};
+
+/// Holds an exclusive lock on a keyspace folder, released when dropped
+///
+/// The lock is taken by the OS on the open file handle, not by the existence
+/// of the file, so a lock file left behind by a crashed process is simply
+/// locked again by the next process.
+pub struct LockFile(#[allow(dead_code)] File);
+
+impl LockFile {
+    /// Locks the keyspace folder.
+    ///
+    /// Returns [`crate::Error::AlreadyOpen`] if the folder is already locked,
+    /// by another process or by another keyspace in this process.
+    pub fn acquire<P: AsRef<Path>>(folder: P) -> crate::Result<Self> {
+        let path = folder.as_ref().join(LOCK_FILE);
+
+        log::trace!("Acquiring lock file at {}", path.display());
+
+        Self::open_locked(&path).map(Self)
+    }
+
+    #[cfg(unix)]
+    fn open_locked(path: &Path) -> crate::Result<File> {
+        use rustix::{
+            fs::{flock, FlockOperation},
+            io::Errno,
+        };
+
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(path)?;
+
+        // NOTE: flock locks belong to the open file description, so a second
+        // handle conflicts even inside the same process
+        match flock(&file, FlockOperation::NonBlockingLockExclusive) {
+            Ok(()) => Ok(file),
+            Err(Errno::WOULDBLOCK) => Err(crate::Error::AlreadyOpen),
+            Err(e) => Err(std::io::Error::from(e).into()),
+        }
+    }
+
+    #[cfg(windows)]
+    fn open_locked(path: &Path) -> crate::Result<File> {
+        use std::os::windows::fs::OpenOptionsExt;
+
+        /// Only allows other handles to delete the file
+        const FILE_SHARE_DELETE: u32 = 0x4;
+
+        /// Another handle holds the file without sharing access
+        const ERROR_SHARING_VIOLATION: i32 = 32;
+
+        std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .share_mode(FILE_SHARE_DELETE)
+            .open(path)
+            .map_err(|e| match e.raw_os_error() {
+                Some(ERROR_SHARING_VIOLATION) => crate::Error::AlreadyOpen,
+                _ => e.into(),
+            })
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    fn open_locked(path: &Path) -> crate::Result<File> {
+        log::warn!("Keyspace folder locking is not supported on this platform");
+
+        std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(path)
+            .map_err(Into::into)
+    }
+}
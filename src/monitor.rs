@@ -12,7 +12,7 @@ use crate::{
     Keyspace,
 };
 use lsm_tree::{AbstractTree, SequenceNumberCounter};
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, PoisonError, RwLock};
 use std_semaphore::Semaphore;
 
 /// Monitors write buffer size & journal size
@@ -57,10 +57,13 @@ impl Monitor {
 
     fn try_reduce_journal_size(&self) {
         log::debug!(
-            "monitor: try flushing affected partitions because journals have passed 50% of threshold"
+            "monitor: try flushing affected partitions because journals have passed {}% of threshold",
+            self.keyspace_config.journal_flush_watermark * 100.0,
         );
 
-        let mut journal_writer = self.journal.get_writer();
+        let Ok(mut journal_writer) = self.journal.try_get_writer() else {
+            return;
+        };
         let mut journal_manager = self.journal_manager.write().expect("lock is poisoned");
 
         let seqno_map = journal_manager.rotate_partitions_to_flush_for_oldest_journal_eviction();
@@ -89,53 +92,52 @@ impl Monitor {
                 .cloned()
                 .collect::<Vec<_>>();
 
-            #[allow(clippy::collapsible_if)]
             if actual_seqno_map
                 .iter()
                 .any(|x| !partitions_names_with_queued_tasks.contains(&x.partition.name))
             {
-                if journal_manager
-                    .rotate_journal(&mut journal_writer, actual_seqno_map)
-                    .is_ok()
+                if let Err(e) =
+                    journal_manager.rotate_journal(&mut journal_writer, actual_seqno_map)
                 {
-                    let mut flush_manager = self.flush_manager.write().expect("lock is poisoned");
-
-                    for (partition, _, yanked_id, yanked_memtable) in seqno_map {
-                        flush_manager.enqueue_task(
-                            partition.name.clone(),
-                            FlushTask {
-                                id: yanked_id,
-                                partition,
-                                sealed_memtable: yanked_memtable,
-                            },
-                        );
-                    }
-
-                    self.flush_semaphore.release();
-
-                    drop(flush_manager);
-                    drop(journal_manager);
-                    drop(journal_writer);
+                    log::error!("journal rotation failed: {e:?}");
                 }
-            } else {
-                self.flush_semaphore.release();
+            } else if let Err(e) = journal_manager.maintenance() {
+                log::error!("journal GC failed: {e:?}");
+            }
 
-                if let Err(e) = journal_manager.maintenance() {
-                    log::error!("journal GC failed: {e:?}");
-                }
+            // IMPORTANT: The memtables were already sealed, so they always need to be
+            // flushed, otherwise they are neither active nor queued for flushing
+            let mut flush_manager = self.flush_manager.write().expect("lock is poisoned");
+
+            for (partition, _, yanked_id, yanked_memtable) in seqno_map {
+                flush_manager.enqueue_task(
+                    partition.name.clone(),
+                    FlushTask {
+                        id: yanked_id,
+                        partition,
+                        sealed_memtable: yanked_memtable,
+                    },
+                );
             }
+
+            self.flush_semaphore.release();
+
+            drop(flush_manager);
+            drop(journal_manager);
+            drop(journal_writer);
         }
     }
 
     fn try_reduce_write_buffer_size(&self) {
         log::trace!(
-            "monitor: flush inactive partition because write buffer has passed 50% of threshold"
+            "monitor: flush inactive partition because write buffer has passed {}% of threshold",
+            self.keyspace_config.write_buffer_flush_watermark * 100.0,
         );
 
         let mut partitions = self
             .partitions
             .read()
-            .expect("lock is poisoned")
+            .unwrap_or_else(PoisonError::into_inner)
             .values()
             .cloned()
             .collect::<Vec<_>>();
@@ -186,12 +188,16 @@ impl Monitor {
         // we never opened a snapshot, we need to pull the watermark up
         //
         // https://github.com/fjall-rs/fjall/discussions/85
-        if (current_seqno - gc_seqno_watermark) > 100 && self.snapshot_tracker.data.is_empty() {
+        let safety_gap = self.snapshot_tracker.safety_gap;
+
+        if (current_seqno - gc_seqno_watermark) > safety_gap
+            && self.snapshot_tracker.data.is_empty()
+        {
             *self
                 .snapshot_tracker
                 .lowest_freed_instant
                 .write()
-                .expect("lock is poisoned") = current_seqno.saturating_sub(100);
+                .unwrap_or_else(PoisonError::into_inner) = current_seqno.saturating_sub(safety_gap);
         }
 
         let jm_size = self
@@ -202,7 +208,8 @@ impl Monitor {
 
         let max_journal_size = self.keyspace_config.max_journaling_size_in_bytes;
 
-        if jm_size as f64 > (max_journal_size as f64 * 0.5) {
+        if jm_size as f64 > (max_journal_size as f64 * self.keyspace_config.journal_flush_watermark)
+        {
             self.try_reduce_journal_size();
             idle = false;
         }
@@ -240,7 +247,9 @@ impl Monitor {
             // Also, As a fail safe, use saturating_sub so it doesn't overflow
             let buffer_size_without_queued_size = write_buffer_size.saturating_sub(queued_size);
 
-            if buffer_size_without_queued_size as f64 > (max_write_buffer_size as f64 * 0.5) {
+            if buffer_size_without_queued_size as f64
+                > (max_write_buffer_size as f64 * self.keyspace_config.write_buffer_flush_watermark)
+            {
                 self.try_reduce_write_buffer_size();
                 idle = false;
             }
@@ -0,0 +1,148 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+use lsm_tree::{InternalValue, Memtable, SeqNo, ValueType};
+use std::ops::{Bound, RangeBounds};
+
+/// Maps a key inside a key range to a number, so distances between keys can be compared.
+///
+/// Only looks at the first 8 bytes after the common prefix of the key range,
+/// which is good enough for an estimate.
+fn key_position(key: &[u8], prefix_len: usize) -> f64 {
+    let mut bytes = [0; 8];
+
+    for (dst, src) in bytes.iter_mut().zip(key.iter().skip(prefix_len)) {
+        *dst = *src;
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let position = u64::from_be_bytes(bytes) as f64;
+
+    position
+}
+
+/// Counts the items (including old versions and tombstones) of a memtable that are inside the range.
+///
+/// Only the items inside the range are visited.
+pub fn memtable_count<K: AsRef<[u8]>, R: RangeBounds<K>>(memtable: &Memtable, range: &R) -> usize {
+    // NOTE: Internal keys are sorted by user key, then by descending seqno
+    let internal_key = |key: &K, seqno: SeqNo| {
+        InternalValue::from_components(key.as_ref(), [], seqno, ValueType::Value).key
+    };
+
+    let lo = match range.start_bound() {
+        Bound::Included(start) => Bound::Included(internal_key(start, SeqNo::MAX)),
+        Bound::Excluded(start) => Bound::Excluded(internal_key(start, 0)),
+        Bound::Unbounded => Bound::Unbounded,
+    };
+
+    let hi = match range.end_bound() {
+        Bound::Included(end) => Bound::Included(internal_key(end, 0)),
+        Bound::Excluded(end) => Bound::Excluded(internal_key(end, SeqNo::MAX)),
+        Bound::Unbounded => Bound::Unbounded,
+    };
+
+    memtable.items.range((lo, hi)).count()
+}
+
+/// Estimates how much of the (inclusive) key range `min..=max` is covered by `range`,
+/// assuming keys are uniformly distributed.
+///
+/// Returns a value between 0.0 and 1.0.
+pub fn overlap_fraction<K: AsRef<[u8]>, R: RangeBounds<K>>(
+    min: &[u8],
+    max: &[u8],
+    range: &R,
+) -> f64 {
+    let start = match range.start_bound() {
+        Bound::Included(key) if key.as_ref() > max => return 0.0,
+        Bound::Excluded(key) if key.as_ref() >= max => return 0.0,
+        Bound::Included(key) | Bound::Excluded(key) if key.as_ref() > min => key.as_ref(),
+        _ => min,
+    };
+
+    let end = match range.end_bound() {
+        Bound::Included(key) if key.as_ref() < min => return 0.0,
+        Bound::Excluded(key) if key.as_ref() <= min => return 0.0,
+        Bound::Included(key) | Bound::Excluded(key) if key.as_ref() < max => key.as_ref(),
+        _ => max,
+    };
+
+    if start > end {
+        return 0.0;
+    }
+
+    let prefix_len = min.iter().zip(max).take_while(|(a, b)| a == b).count();
+
+    let width = key_position(max, prefix_len) - key_position(min, prefix_len);
+
+    if width <= 0.0 {
+        // NOTE: Segment only covers a single key (or the keys differ only after
+        // the first 8 distinguishing bytes), and the range overlaps it
+        return 1.0;
+    }
+
+    let covered = key_position(end, prefix_len) - key_position(start, prefix_len);
+
+    (covered / width).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    #[test]
+    fn memtable_count_versions() {
+        let memtable = Memtable::default();
+
+        for (seqno, key) in [b"a", b"b", b"b", b"c", b"c", b"c", b"d"]
+            .iter()
+            .enumerate()
+        {
+            memtable.insert(InternalValue::from_components(
+                *key,
+                *b"abc",
+                seqno as SeqNo,
+                ValueType::Value,
+            ));
+        }
+        memtable.insert(InternalValue::new_tombstone(*b"b", 7));
+
+        assert_eq!(8, memtable_count::<&[u8], _>(&memtable, &..));
+        assert_eq!(6, memtable_count(&memtable, &(b"b".as_slice()..=b"c")));
+        assert_eq!(3, memtable_count(&memtable, &(b"b".as_slice()..b"c")));
+        assert_eq!(4, memtable_count(&memtable, &(b"c".as_slice()..)));
+        assert_eq!(0, memtable_count(&memtable, &(b"bb".as_slice()..b"c")));
+        assert_eq!(0, memtable_count(&memtable, &(b"d".as_slice()..b"a")));
+    }
+
+    #[test]
+    fn overlap_fraction_disjoint() {
+        assert_eq!(0.0, overlap_fraction(b"c", b"f", &(b"a".as_slice()..=b"b")));
+        assert_eq!(0.0, overlap_fraction(b"c", b"f", &(b"a".as_slice()..b"c")));
+        assert_eq!(0.0, overlap_fraction(b"c", b"f", &(b"g".as_slice()..)));
+        assert_eq!(0.0, overlap_fraction(b"c", b"f", &(b"e".as_slice()..b"d")));
+    }
+
+    #[test]
+    fn overlap_fraction_full() {
+        assert_eq!(1.0, overlap_fraction::<&[u8], _>(b"c", b"f", &..));
+        assert_eq!(1.0, overlap_fraction(b"c", b"f", &(b"a".as_slice()..=b"z")));
+        assert_eq!(1.0, overlap_fraction(b"c", b"c", &(b"c".as_slice()..=b"c")));
+    }
+
+    #[test]
+    fn overlap_fraction_partial() {
+        let fraction = overlap_fraction(
+            &[0, 0],
+            &[0, 100],
+            &([0u8, 25].as_slice()..[0, 75].as_slice()),
+        );
+        assert!((fraction - 0.5).abs() < f64::EPSILON);
+
+        let fraction = overlap_fraction(&[7, 0], &[7, 200], &([7u8, 150].as_slice()..));
+        assert!((fraction - 0.25).abs() < f64::EPSILON);
+    }
+}
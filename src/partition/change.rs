@@ -0,0 +1,21 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+use lsm_tree::{SeqNo, UserKey, UserValue, ValueType};
+
+/// A single write to a partition, see [`crate::PartitionHandle::changes_since`]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Change {
+    /// Sequence number the write was made at
+    pub seqno: SeqNo,
+
+    /// Written key
+    pub key: UserKey,
+
+    /// Whether the write inserted or deleted the key
+    pub value_type: ValueType,
+
+    /// Written value, empty for deletions
+    pub value: UserValue,
+}
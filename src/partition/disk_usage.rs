@@ -0,0 +1,40 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+/// Breakdown of the disk space used by a partition, see [`crate::PartitionHandle::disk_usage`]
+///
+/// Journals are shared by all partitions, see [`crate::Keyspace::journal_disk_space`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[allow(clippy::module_name_repetitions)]
+pub struct DiskUsage {
+    /// Total on-disk size of all segments, per level (0 = first level)
+    pub levels: Vec<u64>,
+
+    /// On-disk size of the value log
+    ///
+    /// Always 0 for partitions without key-value separation.
+    pub blobs: u64,
+
+    /// Size of all bloom filters
+    ///
+    /// Bloom filters are stored inside the segment files,
+    /// so they are already accounted for in [`DiskUsage::levels`].
+    ///
+    /// Always 0 if the `bloom` feature is disabled.
+    pub bloom_filters: u64,
+}
+
+impl DiskUsage {
+    /// Returns the total on-disk size of all segments.
+    #[must_use]
+    pub fn segments(&self) -> u64 {
+        self.levels.iter().sum()
+    }
+
+    /// Returns the total on-disk size of the partition.
+    #[must_use]
+    pub fn total(&self) -> u64 {
+        self.segments() + self.blobs
+    }
+}
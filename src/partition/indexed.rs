@@ -0,0 +1,175 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+use crate::{Keyspace, PartitionHandle};
+use lsm_tree::{UserKey, UserValue};
+use std::sync::Arc;
+
+/// Function that extracts index keys from a key-value pair
+pub type IndexFn = dyn Fn(&[u8], &[u8]) -> Vec<Vec<u8>> + Send + Sync + 'static;
+
+/// Suffix of the sibling partition that stores the reverse index
+pub const INDEX_PARTITION_SUFFIX: &str = "#idx";
+
+/// A partition that automatically maintains a reverse index in a sibling partition
+///
+/// Every write to the primary partition also writes (or removes) the
+/// corresponding index entries inside the same atomic batch, so the
+/// index can never diverge from the primary data.
+///
+/// Index entries are stored as `[index key length (u16 BE)][index key][primary key]`.
+#[derive(Clone)]
+#[allow(clippy::module_name_repetitions)]
+pub struct IndexedPartition {
+    keyspace: Keyspace,
+
+    /// Primary partition
+    primary: PartitionHandle,
+
+    /// Sibling partition storing the reverse index
+    index: PartitionHandle,
+
+    index_fn: Arc<IndexFn>,
+}
+
+impl IndexedPartition {
+    pub(crate) fn new(
+        keyspace: Keyspace,
+        primary: PartitionHandle,
+        index: PartitionHandle,
+        index_fn: Arc<IndexFn>,
+    ) -> Self {
+        Self {
+            keyspace,
+            primary,
+            index,
+            index_fn,
+        }
+    }
+
+    fn prefix_for(index_key: &[u8]) -> Vec<u8> {
+        // NOTE: Index keys are limited by the key size (u16) anyway
+        #[allow(clippy::cast_possible_truncation)]
+        let len = index_key.len() as u16;
+
+        let mut prefix = Vec::with_capacity(2 + index_key.len());
+        prefix.extend_from_slice(&len.to_be_bytes());
+        prefix.extend_from_slice(index_key);
+        prefix
+    }
+
+    fn entry_for(index_key: &[u8], primary_key: &[u8]) -> Vec<u8> {
+        let mut entry = Self::prefix_for(index_key);
+        entry.extend_from_slice(primary_key);
+        entry
+    }
+
+    /// Inserts a key-value pair, updating the reverse index atomically.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if an IO error occurs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lock is poisoned.
+    pub fn insert<K: AsRef<[u8]>, V: AsRef<[u8]>>(&self, key: K, value: V) -> crate::Result<()> {
+        let key = key.as_ref();
+        let value = value.as_ref();
+
+        // NOTE: The lock belongs to the primary partition, so it is shared by all handles
+        let _lock = self
+            .primary
+            .indexed_write_lock
+            .lock()
+            .expect("lock is poisoned");
+
+        let mut batch = self.keyspace.batch();
+
+        if let Some(old_value) = self.primary.get(key)? {
+            for index_key in (self.index_fn)(key, &old_value) {
+                batch.remove(&self.index, Self::entry_for(&index_key, key));
+            }
+        }
+
+        for index_key in (self.index_fn)(key, value) {
+            batch.insert(&self.index, Self::entry_for(&index_key, key), []);
+        }
+
+        batch.insert(&self.primary, key, value);
+        batch.commit()
+    }
+
+    /// Removes a key, removing its reverse index entries atomically.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if an IO error occurs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lock is poisoned.
+    pub fn remove<K: AsRef<[u8]>>(&self, key: K) -> crate::Result<()> {
+        let key = key.as_ref();
+
+        let _lock = self
+            .primary
+            .indexed_write_lock
+            .lock()
+            .expect("lock is poisoned");
+
+        let Some(old_value) = self.primary.get(key)? else {
+            return Ok(());
+        };
+
+        let mut batch = self.keyspace.batch();
+
+        for index_key in (self.index_fn)(key, &old_value) {
+            batch.remove(&self.index, Self::entry_for(&index_key, key));
+        }
+
+        batch.remove(&self.primary, key);
+        batch.commit()
+    }
+
+    /// Opens a read-only snapshot of the primary partition.
+    #[must_use]
+    pub fn snapshot(&self) -> crate::Snapshot {
+        self.primary.snapshot()
+    }
+
+    /// Opens a read-only snapshot of the partition storing the reverse index.
+    #[must_use]
+    pub fn index_snapshot(&self) -> crate::Snapshot {
+        self.index.snapshot()
+    }
+
+    /// Retrieves an item from the primary partition.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if an IO error occurs.
+    pub fn get<K: AsRef<[u8]>>(&self, key: K) -> crate::Result<Option<UserValue>> {
+        self.primary.get(key)
+    }
+
+    /// Returns all primary keys that are indexed under the given index key.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if an IO error occurs.
+    pub fn lookup_by_index<K: AsRef<[u8]>>(&self, index_key: K) -> crate::Result<Vec<UserKey>> {
+        let prefix = Self::prefix_for(index_key.as_ref());
+
+        self.index
+            .prefix(&prefix)
+            .map(|item| {
+                let (entry, _) = item?;
+
+                #[allow(clippy::indexing_slicing)]
+                Ok(entry[prefix.len()..].into())
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,16 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+use lsm_tree::{SeqNo, UserValue};
+
+/// A single version of a key, see [`crate::PartitionHandle::get_all_versions`]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[allow(clippy::module_name_repetitions)]
+pub struct KeyVersion {
+    /// Sequence number the version was written at
+    pub seqno: SeqNo,
+
+    /// Written value, or `None` if the key was deleted
+    pub value: Option<UserValue>,
+}
@@ -0,0 +1,87 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+use lsm_tree::{segment::Segment, UserKey};
+
+/// Read-only summary of a single level of a partition's LSM-tree
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[allow(clippy::module_name_repetitions)]
+pub struct LevelSummary {
+    /// Level index (0 = first level)
+    pub level: usize,
+
+    /// Number of segments in the level
+    pub segment_count: usize,
+
+    /// Total on-disk size of all segments in the level, in bytes
+    pub size: u64,
+
+    /// Smallest and largest key covered by the level
+    ///
+    /// `None` if the level is empty.
+    pub key_range: Option<(UserKey, UserKey)>,
+
+    /// Maximum number of segments whose key ranges overlap at any single key
+    ///
+    /// A disjoint (non-empty) level has an overlap depth of 1,
+    /// an empty level has an overlap depth of 0.
+    pub overlap_depth: usize,
+}
+
+impl LevelSummary {
+    pub(crate) fn from_level(idx: usize, level: &[Segment], is_disjoint: bool) -> Self {
+        let segment_count = level.len();
+        let size = level.iter().map(|x| x.metadata.file_size).sum();
+
+        let key_range = {
+            let min = level.iter().map(|x| &x.metadata.key_range.0).min();
+            let max = level.iter().map(|x| &x.metadata.key_range.1).max();
+            min.zip(max).map(|(min, max)| (min.clone(), max.clone()))
+        };
+
+        let overlap_depth = if is_disjoint {
+            usize::from(segment_count > 0)
+        } else {
+            Self::overlap_depth(level)
+        };
+
+        Self {
+            level: idx,
+            segment_count,
+            size,
+            key_range,
+            overlap_depth,
+        }
+    }
+
+    /// Sweeps over all key range boundaries, counting how many
+    /// segments are "open" at the same time.
+    fn overlap_depth(level: &[Segment]) -> usize {
+        // NOTE: Key ranges are inclusive on both sides, so starts need to be
+        // sorted before ends at the same key (false < true)
+        let mut events = level
+            .iter()
+            .flat_map(|x| {
+                let (min, max) = &*x.metadata.key_range;
+                [(min, false), (max, true)]
+            })
+            .collect::<Vec<_>>();
+
+        events.sort();
+
+        let mut depth = 0;
+        let mut max_depth = 0;
+
+        for (_, is_end) in events {
+            if is_end {
+                depth -= 1;
+            } else {
+                depth += 1;
+                max_depth = max_depth.max(depth);
+            }
+        }
+
+        max_depth
+    }
+}
@@ -2,14 +2,29 @@
 // This source code is licensed under both the Apache 2.0 and MIT License
 // (found in the LICENSE-* files in the repository)
 
+mod approximate;
+pub mod change;
+pub mod disk_usage;
+pub mod indexed;
+pub mod key_version;
+pub mod level_summary;
 pub mod name;
+
+#[cfg(feature = "async")]
+mod nonblocking;
+
 pub mod options;
+pub mod segment_summary;
+pub mod verify;
 mod write_delay;
 
 use crate::{
     batch::PartitionKey,
-    compaction::manager::CompactionManager,
-    config::Config as KeyspaceConfig,
+    compaction::{
+        manager::CompactionManager, range::Strategy as RangeCompactionStrategy,
+        ManualCompactionReport,
+    },
+    config::{Config as KeyspaceConfig, WriteStallMode},
     file::{LSM_MANIFEST_FILE, PARTITIONS_FOLDER, PARTITION_CONFIG_FILE, PARTITION_DELETED_MARKER},
     flush::manager::{FlushManager, Task as FlushTask},
     gc::GarbageCollection,
@@ -23,19 +38,25 @@ use crate::{
     write_buffer_manager::WriteBufferManager,
     Error, Keyspace,
 };
+use change::Change;
+use disk_usage::DiskUsage;
+use key_version::KeyVersion;
+use level_summary::LevelSummary;
 use lsm_tree::{
-    gc::Report as GcReport, AbstractTree, AnyTree, KvPair, SequenceNumberCounter, UserKey,
-    UserValue,
+    gc::Report as GcReport, AbstractTree, AnyTree, KvPair, Memtable, SeqNo, SequenceNumberCounter,
+    UserKey, UserValue,
 };
 use options::CreateOptions;
+use segment_summary::SegmentSummary;
 use std::{
     fs::File,
-    ops::RangeBounds,
+    ops::{Bound, RangeBounds},
     path::Path,
-    sync::{atomic::AtomicBool, Arc, RwLock},
+    sync::{atomic::AtomicBool, Arc, Mutex, RwLock},
     time::Duration,
 };
 use std_semaphore::Semaphore;
+use verify::VerifyReport;
 use write_delay::get_write_delay;
 
 #[allow(clippy::module_name_repetitions)]
@@ -92,6 +113,10 @@ pub struct PartitionHandleInner {
 
     /// Snapshot tracker
     pub(crate) snapshot_tracker: SnapshotTracker,
+
+    /// Serializes writes through [`IndexedPartition`](crate::IndexedPartition) handles,
+    /// so index entries of concurrent updates to the same key are not lost
+    pub(crate) indexed_write_lock: Mutex<()>,
 }
 
 impl Drop for PartitionHandleInner {
@@ -192,6 +217,29 @@ impl GarbageCollection for PartitionHandle {
     }
 }
 
+/// Resolves a value read from the index tree into the user value.
+///
+/// For key-value separated partitions, the index tree stores either the value
+/// itself or a pointer into the blob files. Returns `None` if the blob
+/// was already garbage collected.
+fn resolve_value(tree: &AnyTree, value: UserValue) -> crate::Result<Option<UserValue>> {
+    use lsm_tree::{blob_tree::value::MaybeInlineValue, coding::Decode};
+
+    let AnyTree::Blob(tree) = tree else {
+        return Ok(Some(value));
+    };
+
+    let mut cursor = std::io::Cursor::new(value);
+
+    match MaybeInlineValue::decode_from(&mut cursor)? {
+        MaybeInlineValue::Inline(value) => Ok(Some(value)),
+        MaybeInlineValue::Indirect { vhandle, .. } => tree
+            .blobs
+            .get(&vhandle)
+            .map_err(|e| lsm_tree::Error::from(e).into()),
+    }
+}
+
 impl PartitionHandle {
     pub(crate) fn from_keyspace(
         keyspace: &Keyspace,
@@ -214,6 +262,7 @@ impl PartitionHandle {
             is_deleted: AtomicBool::default(),
             is_poisoned: keyspace.is_poisoned.clone(),
             snapshot_tracker: keyspace.snapshot_tracker.clone(),
+            indexed_write_lock: Mutex::default(),
             config,
         }))
     }
@@ -244,7 +293,7 @@ impl PartitionHandle {
 
         let mut base_config = lsm_tree::Config::new(base_folder)
             .descriptor_table(keyspace.config.descriptor_table.clone())
-            .block_cache(keyspace.config.block_cache.clone())
+            .block_cache(keyspace.config.block_cache_for(&name))
             .blob_cache(keyspace.config.blob_cache.clone())
             .data_block_size(config.data_block_size)
             .index_block_size(config.index_block_size)
@@ -284,6 +333,7 @@ impl PartitionHandle {
             is_deleted: AtomicBool::default(),
             is_poisoned: keyspace.is_poisoned.clone(),
             snapshot_tracker: keyspace.snapshot_tracker.clone(),
+            indexed_write_lock: Mutex::default(),
         })))
     }
 
@@ -378,6 +428,66 @@ impl PartitionHandle {
         self.tree.range(range).map(|item| item.map_err(Into::into))
     }
 
+    /// Returns an iterator over a range of items, including the sequence number
+    /// each item was written at.
+    ///
+    /// Sequence numbers are assigned by the keyspace, so they can be used to
+    /// order changes across partitions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fjall::{Config, Keyspace, PartitionCreateOptions};
+    /// #
+    /// # let folder = tempfile::tempdir()?;
+    /// # let keyspace = Config::new(folder).open()?;
+    /// # let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+    /// partition.insert("a", "abc")?;
+    /// partition.insert("f", "abc")?;
+    /// partition.insert("a", "def")?;
+    ///
+    /// let items = partition.range_with_seqno("a"..="f").collect::<Result<Vec<_>, _>>()?;
+    /// assert_eq!(2, items.len());
+    /// assert!(items[0].2 > items[1].2);
+    /// #
+    /// # Ok::<(), fjall::Error>(())
+    /// ```
+    pub fn range_with_seqno<K: AsRef<[u8]>, R: RangeBounds<K>>(
+        &self,
+        range: R,
+    ) -> impl DoubleEndedIterator<Item = crate::Result<(UserKey, UserValue, SeqNo)>> + 'static {
+        // TODO: Bound::map 1.77
+        let lo: Bound<UserKey> = match range.start_bound() {
+            Bound::Included(x) => Bound::Included(x.as_ref().into()),
+            Bound::Excluded(x) => Bound::Excluded(x.as_ref().into()),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+
+        // TODO: Bound::map 1.77
+        let hi: Bound<UserKey> = match range.end_bound() {
+            Bound::Included(x) => Bound::Included(x.as_ref().into()),
+            Bound::Excluded(x) => Bound::Excluded(x.as_ref().into()),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+
+        let tree = self.tree.clone();
+
+        self.index_tree()
+            .create_internal_range(&(lo, hi), None, None)
+            .filter_map(move |item| {
+                let item = match item {
+                    Ok(item) => item,
+                    Err(e) => return Some(Err(e.into())),
+                };
+
+                match resolve_value(&tree, item.value) {
+                    Ok(Some(value)) => Some(Ok((item.key.user_key, value, item.key.seqno))),
+                    Ok(None) => None,
+                    Err(e) => Some(Err(e)),
+                }
+            })
+    }
+
     /// Returns an iterator over a prefixed set of items.
     ///
     /// Avoid using an empty prefix as it may scan a lot of items (unless limited).
@@ -406,6 +516,147 @@ impl PartitionHandle {
             .map(|item| item.map_err(Into::into))
     }
 
+    /// Returns an iterator over a prefixed set of items, starting after the given key.
+    ///
+    /// This allows paginating over a prefix, by passing the last key of the previous page.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fjall::{Config, Keyspace, PartitionCreateOptions};
+    /// #
+    /// # let folder = tempfile::tempdir()?;
+    /// # let keyspace = Config::new(folder).open()?;
+    /// # let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+    /// partition.insert("user#1", "abc")?;
+    /// partition.insert("user#2", "abc")?;
+    /// partition.insert("user#3", "abc")?;
+    ///
+    /// let page = partition.prefix_after("user#", "user#1").take(1).collect::<Result<Vec<_>, _>>()?;
+    /// assert_eq!(b"user#2", &*page[0].0);
+    /// #
+    /// # Ok::<(), fjall::Error>(())
+    /// ```
+    pub fn prefix_after<K: AsRef<[u8]>, C: AsRef<[u8]>>(
+        &self,
+        prefix: K,
+        start_after: C,
+    ) -> impl DoubleEndedIterator<Item = crate::Result<KvPair>> + 'static {
+        let (start, end) = lsm_tree::range::prefix_to_range(prefix.as_ref());
+        let cursor: UserKey = start_after.as_ref().into();
+
+        let start = match start {
+            Bound::Included(prefix) if prefix > cursor => Bound::Included(prefix),
+            _ => Bound::Excluded(cursor),
+        };
+
+        self.bounded_range(start, end)
+    }
+
+    /// Returns an iterator over a prefixed set of items, ending before the given key.
+    ///
+    /// This allows paginating backwards over a prefix (using `.rev()`),
+    /// by passing the last key of the previous page.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fjall::{Config, Keyspace, PartitionCreateOptions};
+    /// #
+    /// # let folder = tempfile::tempdir()?;
+    /// # let keyspace = Config::new(folder).open()?;
+    /// # let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+    /// partition.insert("user#1", "abc")?;
+    /// partition.insert("user#2", "abc")?;
+    /// partition.insert("user#3", "abc")?;
+    ///
+    /// let page = partition.prefix_before("user#", "user#3").rev().take(1).collect::<Result<Vec<_>, _>>()?;
+    /// assert_eq!(b"user#2", &*page[0].0);
+    /// #
+    /// # Ok::<(), fjall::Error>(())
+    /// ```
+    pub fn prefix_before<K: AsRef<[u8]>, C: AsRef<[u8]>>(
+        &self,
+        prefix: K,
+        end_before: C,
+    ) -> impl DoubleEndedIterator<Item = crate::Result<KvPair>> + 'static {
+        let (start, end) = lsm_tree::range::prefix_to_range(prefix.as_ref());
+        let cursor: UserKey = end_before.as_ref().into();
+
+        let end = match end {
+            Bound::Excluded(end) if end < cursor => Bound::Excluded(end),
+            _ => Bound::Excluded(cursor),
+        };
+
+        self.bounded_range(start, end)
+    }
+
+    /// Returns an iterator over the given bounds, which may be empty (start > end).
+    fn bounded_range(
+        &self,
+        start: Bound<UserKey>,
+        end: Bound<UserKey>,
+    ) -> impl DoubleEndedIterator<Item = crate::Result<KvPair>> + 'static {
+        let is_empty = match (&start, &end) {
+            (Bound::Included(start), Bound::Included(end)) => start > end,
+            (Bound::Included(start) | Bound::Excluded(start), Bound::Excluded(end))
+            | (Bound::Excluded(start), Bound::Included(end)) => start >= end,
+            _ => false,
+        };
+
+        let iter: Box<dyn DoubleEndedIterator<Item = lsm_tree::Result<KvPair>>> = if is_empty {
+            Box::new(std::iter::empty())
+        } else {
+            self.tree.range((start, end))
+        };
+
+        iter.map(|item| item.map_err(Into::into))
+    }
+
+    /// Folds over a range of items, returning only the accumulated result.
+    ///
+    /// This is useful for aggregations (sum, max, count, ...) that do not
+    /// need to hand every key-value pair to the caller.
+    ///
+    /// Avoid using full or unbounded ranges as they may scan a lot of items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fjall::{Config, Keyspace, PartitionCreateOptions};
+    /// #
+    /// # let folder = tempfile::tempdir()?;
+    /// # let keyspace = Config::new(folder).open()?;
+    /// # let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+    /// partition.insert("a", "abc")?;
+    /// partition.insert("f", "abc")?;
+    /// partition.insert("g", "abc")?;
+    ///
+    /// let bytes = partition.fold_range("a"..="f", 0, |acc, _, v| acc + v.len())?;
+    /// assert_eq!(6, bytes);
+    /// #
+    /// # Ok::<(), fjall::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if an IO error occurs.
+    pub fn fold_range<K: AsRef<[u8]>, R: RangeBounds<K>, A>(
+        &self,
+        range: R,
+        init: A,
+        mut f: impl FnMut(A, &[u8], &[u8]) -> A,
+    ) -> crate::Result<A> {
+        let mut acc = init;
+
+        for item in self.tree.range(range) {
+            let (k, v) = item?;
+            acc = f(acc, &k, &v);
+        }
+
+        Ok(acc)
+    }
+
     /// Approximates the amount of items in the partition.
     ///
     /// For update -or delete-heavy workloads, this value will
@@ -438,6 +689,69 @@ impl PartitionHandle {
         self.tree.approximate_len()
     }
 
+    /// Estimates the amount of items in a range, without reading any blocks.
+    ///
+    /// For every segment that overlaps the range, its item count is scaled by how much of the
+    /// segment's key range is covered, assuming keys are uniformly distributed.
+    /// Memtables are scanned, so their items are counted exactly.
+    ///
+    /// Like [`PartitionHandle::approximate_len`], old versions and tombstones are counted as items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fjall::{Config, Keyspace, PartitionCreateOptions};
+    /// #
+    /// # let folder = tempfile::tempdir()?;
+    /// # let keyspace = Config::new(folder).open()?;
+    /// # let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+    /// partition.insert("a", "abc")?;
+    /// partition.insert("b", "abc")?;
+    /// partition.insert("c", "abc")?;
+    ///
+    /// assert_eq!(2, partition.approximate_len_range("b"..));
+    /// #
+    /// # Ok::<(), fjall::Error>(())
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if a lock is poisoned.
+    #[must_use]
+    pub fn approximate_len_range<K: AsRef<[u8]>, R: RangeBounds<K>>(&self, range: R) -> usize {
+        // NOTE: Only the items inside the range are visited, so the
+        // active memtable is not locked for a scan of all its items
+        let memtable_count = self
+            .with_memtables(|active_memtable, sealed_memtables| {
+                std::iter::once(active_memtable)
+                    .chain(sealed_memtables.iter().map(|memtable| &**memtable))
+                    .map(|memtable| approximate::memtable_count(memtable, &range))
+                    .sum::<usize>()
+            })
+            .expect("lock is poisoned");
+
+        let manifest = self.index_tree().levels.read().expect("lock is poisoned");
+
+        let segment_count = manifest
+            .iter()
+            .map(|segment| {
+                let (min, max) = &*segment.metadata.key_range;
+
+                #[allow(clippy::cast_precision_loss)]
+                let item_count = segment.metadata.item_count as f64;
+
+                item_count * approximate::overlap_fraction(min, max, &range)
+            })
+            .sum::<f64>();
+
+        drop(manifest);
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let segment_count = segment_count.round() as usize;
+
+        memtable_count + segment_count
+    }
+
     /// Scans the entire partition, returning the amount of items.
     ///
     /// ###### Caution
@@ -481,6 +795,48 @@ impl PartitionHandle {
         Ok(count)
     }
 
+    /// Scans the entire prefix, returning the amount of live items.
+    ///
+    /// Deleted keys are not counted.
+    ///
+    /// For key-value separated partitions, values are not fetched
+    /// from the blob files, only the index tree is scanned.
+    ///
+    /// ###### Caution
+    ///
+    /// This operation scans the entire prefix: O(n) complexity!
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fjall::{Config, Keyspace, PartitionCreateOptions};
+    /// #
+    /// # let folder = tempfile::tempdir()?;
+    /// # let keyspace = Config::new(folder).open()?;
+    /// # let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+    /// partition.insert("user#1", "abc")?;
+    /// partition.insert("user#2", "abc")?;
+    /// partition.insert("post#1", "abc")?;
+    /// partition.remove("user#2")?;
+    /// assert_eq!(partition.prefix_len("user#")?, 1);
+    /// #
+    /// # Ok::<(), fjall::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if an IO error occurs.
+    pub fn prefix_len<K: AsRef<[u8]>>(&self, prefix: K) -> crate::Result<usize> {
+        let mut count = 0;
+
+        for kv in self.index_tree().prefix(prefix) {
+            let _ = kv?;
+            count += 1;
+        }
+
+        Ok(count)
+    }
+
     /// Returns `true` if the partition is empty.
     ///
     /// This operation has O(1) complexity.
@@ -558,6 +914,80 @@ impl PartitionHandle {
         Ok(self.tree.get(key)?)
     }
 
+    /// Retrieves an item from the partition, including the sequence number
+    /// it was written at.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fjall::{Config, Keyspace, PartitionCreateOptions};
+    /// #
+    /// # let folder = tempfile::tempdir()?;
+    /// # let keyspace = Config::new(folder).open()?;
+    /// # let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+    /// let seqno = keyspace.instant();
+    /// partition.insert("a", "my_value")?;
+    ///
+    /// let item = partition.get_with_seqno("a")?;
+    /// assert_eq!(Some(("my_value".as_bytes().into(), seqno)), item);
+    /// #
+    /// # Ok::<(), fjall::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if an IO error occurs.
+    pub fn get_with_seqno<K: AsRef<[u8]>>(
+        &self,
+        key: K,
+    ) -> crate::Result<Option<(UserValue, SeqNo)>> {
+        let Some(item) = self.index_tree().get_internal_entry(key, true, None)? else {
+            return Ok(None);
+        };
+
+        Ok(resolve_value(&self.tree, item.value)?.map(|value| (value, item.key.seqno)))
+    }
+
+    /// Retrieves multiple items from the partition, returning their values in input order.
+    ///
+    /// All keys are read from the same snapshot, so the result is consistent
+    /// even if the partition is written to concurrently.
+    ///
+    /// Note: Every key is a separate point read, so this is not faster than
+    /// calling [`PartitionHandle::get`] for each key. A batched lookup needs
+    /// access to segment internals that `lsm-tree` does not expose.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fjall::{Config, Keyspace, PartitionCreateOptions};
+    /// #
+    /// # let folder = tempfile::tempdir()?;
+    /// # let keyspace = Config::new(folder).open()?;
+    /// # let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+    /// partition.insert("a", "abc")?;
+    /// partition.insert("c", "def")?;
+    ///
+    /// let items = partition.multi_get(&["c", "b", "a"])?;
+    /// assert_eq!(
+    ///     vec![Some("def".as_bytes().into()), None, Some("abc".as_bytes().into())],
+    ///     items,
+    /// );
+    /// #
+    /// # Ok::<(), fjall::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if an IO error occurs.
+    pub fn multi_get<K: AsRef<[u8]>>(&self, keys: &[K]) -> crate::Result<Vec<Option<UserValue>>> {
+        let snapshot = self.snapshot();
+
+        keys.iter()
+            .map(|key| snapshot.get(key).map_err(Into::into))
+            .collect()
+    }
+
     /// Retrieves the size of an item from the partition.
     ///
     /// # Examples
@@ -655,13 +1085,42 @@ impl PartitionHandle {
         Ok(())
     }
 
+    /// Runs `f` on the active memtable and the sealed memtables that are not flushed yet.
+    ///
+    /// The journal lock is held while the memtables are captured: a rotation holds it
+    /// from sealing the active memtable until the sealed memtable is handed to the flush
+    /// manager, so no memtable can be missed while it is in between.
+    /// Memtables that are flushed afterwards are still captured, so reading segments after
+    /// calling this may only yield duplicates, but never miss items.
+    fn with_memtables<T>(
+        &self,
+        f: impl FnOnce(&Memtable, &[Arc<Memtable>]) -> T,
+    ) -> crate::Result<T> {
+        let journal_writer = self.journal.try_get_writer()?;
+
+        let sealed_memtables = self
+            .flush_manager
+            .read()
+            .expect("lock is poisoned")
+            .sealed_memtables(&self.name);
+
+        let active_memtable = self.tree.lock_active_memtable();
+
+        let result = f(&active_memtable, &sealed_memtables);
+
+        drop(active_memtable);
+        drop(journal_writer);
+
+        Ok(result)
+    }
+
     /// Returns `true` if the memtable was indeed rotated.
     #[doc(hidden)]
     pub fn rotate_memtable(&self) -> crate::Result<bool> {
         log::debug!("Rotating memtable {:?}", self.name);
 
         log::trace!("partition: acquiring journal lock");
-        let mut journal = self.journal.get_writer();
+        let mut journal = self.journal.try_get_writer()?;
 
         // Rotate memtable
         let Some((yanked_id, yanked_memtable)) = self.tree.rotate_memtable() else {
@@ -672,22 +1131,7 @@ impl PartitionHandle {
         log::trace!("partition: acquiring journal manager lock");
         let mut journal_manager = self.journal_manager.write().expect("lock is poisoned");
 
-        let seqno_map = {
-            let partitions = self.partitions.write().expect("lock is poisoned");
-
-            let mut seqnos = Vec::with_capacity(partitions.len());
-
-            for partition in partitions.values() {
-                if let Some(lsn) = partition.tree.get_highest_memtable_seqno() {
-                    seqnos.push(EvictionWatermark {
-                        lsn,
-                        partition: partition.clone(),
-                    });
-                }
-            }
-
-            seqnos
-        };
+        let seqno_map = self.eviction_watermarks();
 
         journal_manager.rotate_journal(&mut journal, seqno_map)?;
 
@@ -713,6 +1157,61 @@ impl PartitionHandle {
         Ok(true)
     }
 
+    /// Returns the highest memtable seqno of every partition,
+    /// used to seal the active journal.
+    fn eviction_watermarks(&self) -> Vec<EvictionWatermark> {
+        let partitions = self.partitions.write().expect("lock is poisoned");
+
+        let mut seqnos = Vec::with_capacity(partitions.len());
+
+        for partition in partitions.values() {
+            if let Some(lsn) = partition.tree.get_highest_memtable_seqno() {
+                seqnos.push(EvictionWatermark {
+                    lsn,
+                    partition: partition.clone(),
+                });
+            }
+        }
+
+        drop(partitions);
+
+        seqnos
+    }
+
+    /// Seals the active journal if it has grown past `Config::journal_rotation_size`.
+    ///
+    /// Unlike [`PartitionHandle::rotate_memtable`], no memtable is rotated,
+    /// the sealed journal is evicted once all its partitions have flushed past its watermarks.
+    #[allow(clippy::significant_drop_tightening)]
+    pub(crate) fn check_journal_rotation(&self) -> crate::Result<()> {
+        let Some(rotation_size) = self.keyspace_config.journal_rotation_size else {
+            return Ok(());
+        };
+
+        let mut journal = self.journal.try_get_writer()?;
+
+        // NOTE: Only counts bytes written since the journal writer was opened,
+        // so a recovered journal may grow a bit larger before being rotated
+        if journal.written_bytes() < rotation_size {
+            return Ok(());
+        }
+
+        log::debug!("Rotating journal because it reached {rotation_size}B");
+
+        let mut journal_manager = self.journal_manager.write().expect("lock is poisoned");
+
+        let seqno_map = self.eviction_watermarks();
+
+        journal_manager
+            .rotate_journal(&mut journal, seqno_map)
+            .map_err(|e| {
+                self.is_poisoned
+                    .store(true, std::sync::atomic::Ordering::Relaxed);
+
+                e
+            })
+    }
+
     fn check_journal_size(&self) {
         loop {
             let bytes = self
@@ -790,18 +1289,23 @@ impl PartitionHandle {
     }
 
     pub(crate) fn check_write_buffer_size(&self, initial_size: u64) {
+        // NOTE: Writers are rejected before writing instead, see `WriteBufferManager::check_saturation`
+        if self.keyspace_config.write_stall_mode == WriteStallMode::Error {
+            return;
+        }
+
         let limit = self.keyspace_config.max_write_buffer_size_in_bytes;
 
         if initial_size > limit {
-            let p90_limit = (limit as f64) * 0.9;
+            let stall_limit = (limit as f64) * self.keyspace_config.write_stall_threshold;
 
             loop {
                 let bytes = self.write_buffer_manager.get();
 
                 if bytes < limit {
-                    if bytes as f64 > p90_limit {
+                    if bytes as f64 > stall_limit {
                         log::info!(
-                            "partition: write stall because 90% write buffer threshold has been reached"
+                            "partition: write stall because write buffer stall threshold has been reached"
                         );
                         std::thread::sleep(std::time::Duration::from_millis(100));
                     }
@@ -820,21 +1324,880 @@ impl PartitionHandle {
         self.tree.segment_count()
     }
 
-    /// Opens a snapshot of this partition.
-    #[must_use]
-    pub fn snapshot(&self) -> crate::Snapshot {
-        self.snapshot_at(self.seqno.get())
-    }
-
-    /// Opens a snapshot of this partition with a given sequence number.
-    #[must_use]
-    pub fn snapshot_at(&self, seqno: crate::Instant) -> crate::Snapshot {
+    /// Returns the highest sequence number that has been flushed to disk segments.
+    ///
+    /// Writes up to (and including) this seqno no longer depend on the journal.
+    /// Returns `None` if nothing has been flushed yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fjall::{Config, Keyspace, PartitionCreateOptions};
+    /// #
+    /// # let folder = tempfile::tempdir()?;
+    /// # let keyspace = Config::new(folder).open()?;
+    /// # let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+    /// partition.insert("a", "abc")?;
+    /// assert_eq!(None, partition.durable_seqno());
+    ///
+    /// partition.rotate_memtable_and_wait()?;
+    /// assert_eq!(partition.current_seqno(), partition.durable_seqno());
+    /// #
+    /// # Ok::<(), fjall::Error>(())
+    /// ```
+    #[must_use]
+    pub fn durable_seqno(&self) -> Option<crate::Instant> {
+        self.tree.get_highest_persisted_seqno()
+    }
+
+    /// Returns the highest sequence number written to the partition,
+    /// including writes that are still in memtables.
+    ///
+    /// Returns `None` if the partition is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fjall::{Config, Keyspace, PartitionCreateOptions};
+    /// #
+    /// # let folder = tempfile::tempdir()?;
+    /// # let keyspace = Config::new(folder).open()?;
+    /// # let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+    /// assert_eq!(None, partition.current_seqno());
+    ///
+    /// partition.insert("a", "abc")?;
+    /// assert!(partition.current_seqno().is_some());
+    /// #
+    /// # Ok::<(), fjall::Error>(())
+    /// ```
+    #[must_use]
+    pub fn current_seqno(&self) -> Option<crate::Instant> {
+        self.tree.get_highest_seqno()
+    }
+
+    /// Returns the LSM-tree that holds the partition's keys
+    /// (which is the index tree for key-value separated partitions).
+    fn index_tree(&self) -> &lsm_tree::Tree {
+        match &self.tree {
+            AnyTree::Standard(tree) => tree,
+            AnyTree::Blob(tree) => &tree.index.0,
+        }
+    }
+
+    /// Returns the keyspace's segment target size, if set, raised to at least 4 data blocks.
+    fn segment_target_size(&self) -> Option<u64> {
+        let min_target_size = 4 * u64::from(self.config.data_block_size);
+
+        self.keyspace_config
+            .segment_target_size
+            .map(|bytes| bytes.max(min_target_size))
+    }
+
+    /// Returns the compaction strategy, using the keyspace's segment target size, if set.
+    pub(crate) fn compaction_strategy(
+        &self,
+    ) -> Arc<dyn lsm_tree::compaction::CompactionStrategy + Send + Sync> {
+        self.config
+            .compaction_strategy
+            .with_target_size(self.segment_target_size())
+    }
+
+    fn segment_ids(&self) -> crate::HashSet<lsm_tree::SegmentId> {
+        self.index_tree()
+            .levels
+            .read()
+            .expect("lock is poisoned")
+            .iter()
+            .map(|segment| segment.metadata.id)
+            .collect()
+    }
+
+    /// Runs the partition's compaction strategy until it produces no more work,
+    /// blocking the caller.
+    ///
+    /// This is useful to reduce read amplification before a read-heavy job,
+    /// instead of waiting for background compactions to catch up.
+    /// Background compactions may still run concurrently; segments that are
+    /// being compacted by another thread are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fjall::{Config, PartitionCreateOptions};
+    /// #
+    /// # let folder = tempfile::tempdir()?;
+    /// # let keyspace = Config::new(folder).open()?;
+    /// # let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+    /// partition.insert("a", "abc")?;
+    /// partition.rotate_memtable_and_wait()?;
+    ///
+    /// let report = partition.compact_manually()?;
+    /// println!("merged {} segments", report.segments_merged);
+    /// #
+    /// # Ok::<(), fjall::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if an IO error occurs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lock is poisoned.
+    pub fn compact_manually(&self) -> crate::Result<ManualCompactionReport> {
+        let strategy = self.compaction_strategy();
+        let disk_space_before = self.disk_space();
+
+        let mut report = ManualCompactionReport::default();
+
+        loop {
+            let ids_before = self.segment_ids();
+
+            self.tree.compact(
+                strategy.clone(),
+                self.snapshot_tracker.get_seqno_safe_to_gc(),
+            )?;
+
+            let ids_after = self.segment_ids();
+
+            // NOTE: Only count consumed segments, because flushes may add new ones concurrently
+            let merged = ids_before.difference(&ids_after).count();
+
+            if merged == 0 {
+                break;
+            }
+
+            report.segments_merged += merged;
+        }
+
+        report.bytes_reclaimed = disk_space_before.saturating_sub(self.disk_space());
+
+        log::debug!(
+            "manual compaction of {:?}: merged {} segments, reclaimed {}B",
+            self.name,
+            report.segments_merged,
+            report.bytes_reclaimed,
+        );
+
+        Ok(report)
+    }
+
+    /// Merges all segments that overlap the given key range, blocking the caller.
+    ///
+    /// Segments outside the key range are left untouched, unless they overlap
+    /// a segment that needs to be merged. This is useful to reclaim the space
+    /// of a region after deleting many keys in it.
+    ///
+    /// Only data in segments is compacted, so the active memtable may need
+    /// to be rotated first. Versions that are still visible to snapshots
+    /// (see [`Keyspace::gc_now`]) are kept, and tombstones are only dropped
+    /// once no snapshot can see the merged data anymore.
+    ///
+    /// If another compaction is working on the key range, nothing is done.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fjall::{Config, PartitionCreateOptions};
+    /// #
+    /// # let folder = tempfile::tempdir()?;
+    /// # let keyspace = Config::new(folder).open()?;
+    /// # let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+    /// partition.insert("a", "abc")?;
+    /// partition.insert("b", "abc")?;
+    /// partition.rotate_memtable_and_wait()?;
+    ///
+    /// partition.remove("a")?;
+    /// partition.rotate_memtable_and_wait()?;
+    ///
+    /// let report = partition.compact_range("a".."b")?;
+    /// assert_eq!(2, report.segments_merged);
+    /// #
+    /// # Ok::<(), fjall::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if an IO error occurs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lock is poisoned.
+    pub fn compact_range<K: AsRef<[u8]>, R: RangeBounds<K>>(
+        &self,
+        range: R,
+    ) -> crate::Result<ManualCompactionReport> {
+        // TODO: Bound::map 1.77
+        let start: Bound<UserKey> = match range.start_bound() {
+            Bound::Included(k) => Bound::Included(k.as_ref().into()),
+            Bound::Excluded(k) => Bound::Excluded(k.as_ref().into()),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+        // TODO: Bound::map 1.77
+        let end: Bound<UserKey> = match range.end_bound() {
+            Bound::Included(k) => Bound::Included(k.as_ref().into()),
+            Bound::Excluded(k) => Bound::Excluded(k.as_ref().into()),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+
+        let target_size = self
+            .segment_target_size()
+            .unwrap_or_else(|| self.config.compaction_strategy.target_size());
+
+        let disk_space_before = self.disk_space();
+        let ids_before = self.segment_ids();
+
+        let gc_watermark = self.snapshot_tracker.get_seqno_safe_to_gc();
+
+        self.tree.compact(
+            Arc::new(RangeCompactionStrategy::new(
+                (start, end),
+                target_size,
+                gc_watermark,
+            )),
+            gc_watermark,
+        )?;
+
+        let report = ManualCompactionReport {
+            segments_merged: ids_before.difference(&self.segment_ids()).count(),
+            bytes_reclaimed: disk_space_before.saturating_sub(self.disk_space()),
+        };
+
+        log::debug!(
+            "range compaction of {:?}: merged {} segments, reclaimed {}B",
+            self.name,
+            report.segments_merged,
+            report.bytes_reclaimed,
+        );
+
+        Ok(report)
+    }
+
+    /// Returns a read-only summary of each level of the partition's LSM-tree.
+    ///
+    /// For each level, reports the number of segments, their total size on disk,
+    /// the key range spanned by the level, and how deeply segments overlap within it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fjall::{Config, PartitionCreateOptions};
+    /// #
+    /// # let folder = tempfile::tempdir()?;
+    /// # let keyspace = Config::new(folder).open()?;
+    /// # let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+    /// partition.insert("a", "abc")?;
+    /// partition.rotate_memtable_and_wait()?;
+    ///
+    /// let levels = partition.level_summary();
+    /// assert_eq!(1, levels[0].segment_count);
+    /// #
+    /// # Ok::<(), fjall::Error>(())
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the level manifest lock is poisoned.
+    #[must_use]
+    pub fn level_summary(&self) -> Vec<LevelSummary> {
+        let manifest = self.index_tree().levels.read().expect("lock is poisoned");
+
+        manifest
+            .levels
+            .iter()
+            .enumerate()
+            .map(|(idx, level)| LevelSummary::from_level(idx, &level.segments, level.is_disjoint))
+            .collect()
+    }
+
+    /// Returns a breakdown of the disk space used by the partition.
+    ///
+    /// The sizes are summed up from in-memory segment metadata,
+    /// so no files are accessed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fjall::{Config, Keyspace, PartitionCreateOptions};
+    /// #
+    /// # let folder = tempfile::tempdir()?;
+    /// # let keyspace = Config::new(folder).open()?;
+    /// # let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+    /// partition.insert("a", "abc")?;
+    /// partition.rotate_memtable_and_wait()?;
+    ///
+    /// let usage = partition.disk_usage();
+    /// assert!(usage.levels[0] > 0);
+    /// assert_eq!(partition.disk_space(), usage.total());
+    /// #
+    /// # Ok::<(), fjall::Error>(())
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lock is poisoned.
+    #[must_use]
+    pub fn disk_usage(&self) -> DiskUsage {
+        let manifest = self.index_tree().levels.read().expect("lock is poisoned");
+
+        let levels = manifest
+            .levels
+            .iter()
+            .map(|level| level.segments.iter().map(|x| x.metadata.file_size).sum())
+            .collect();
+
+        drop(manifest);
+
+        let blobs = match &self.tree {
+            AnyTree::Standard(_) => 0,
+            AnyTree::Blob(tree) => tree.blobs.manifest.disk_space_used(),
+        };
+
+        #[cfg(feature = "bloom")]
+        let bloom_filters = self.tree.bloom_filter_size() as u64;
+
+        #[cfg(not(feature = "bloom"))]
+        let bloom_filters = 0;
+
+        DiskUsage {
+            levels,
+            blobs,
+            bloom_filters,
+        }
+    }
+
+    /// Returns a read-only summary of every segment of the partition's LSM-tree,
+    /// ordered by level.
+    ///
+    /// The summaries are taken from in-memory segment metadata under the level manifest
+    /// lock, so this does not read any blocks or open any files.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fjall::{Config, PartitionCreateOptions};
+    /// #
+    /// # let folder = tempfile::tempdir()?;
+    /// # let keyspace = Config::new(folder).open()?;
+    /// # let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+    /// partition.insert("a", "abc")?;
+    /// partition.insert("z", "abc")?;
+    /// partition.rotate_memtable_and_wait()?;
+    ///
+    /// let segments = partition.segment_summary();
+    /// assert_eq!((b"a".into(), b"z".into()), segments[0].key_range);
+    /// #
+    /// # Ok::<(), fjall::Error>(())
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the level manifest lock is poisoned.
+    #[must_use]
+    pub fn segment_summary(&self) -> Vec<SegmentSummary> {
+        let manifest = self.index_tree().levels.read().expect("lock is poisoned");
+
+        manifest
+            .levels
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, level)| {
+                level
+                    .segments
+                    .iter()
+                    .map(move |segment| SegmentSummary::from_segment(idx, segment))
+            })
+            .collect()
+    }
+
+    /// Returns every version of a key that is still retained, newest first.
+    ///
+    /// Deletions are included as versions without a value.
+    /// Versions that have been dropped by compaction (because no snapshot
+    /// could see them anymore) are not returned.
+    ///
+    /// For key-value separated partitions, versions whose value has already
+    /// been dropped by blob garbage collection are skipped.
+    ///
+    /// ###### Caution
+    ///
+    /// This operation scans every memtable and every segment
+    /// whose key range contains the key, so it should only be used for
+    /// auditing or debugging purposes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fjall::{Config, Keyspace, PartitionCreateOptions};
+    /// #
+    /// # let folder = tempfile::tempdir()?;
+    /// # let keyspace = Config::new(folder).open()?;
+    /// # let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+    /// partition.insert("a", "abc")?;
+    /// partition.insert("a", "def")?;
+    /// partition.remove("a")?;
+    ///
+    /// let versions = partition.get_all_versions("a")?;
+    /// assert_eq!(3, versions.len());
+    /// assert_eq!(None, versions[0].value);
+    /// assert_eq!(Some(b"def".into()), versions[1].value);
+    /// assert_eq!(Some(b"abc".into()), versions[2].value);
+    /// #
+    /// # Ok::<(), fjall::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if an IO error occurs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a lock is poisoned.
+    pub fn get_all_versions<K: AsRef<[u8]>>(&self, key: K) -> crate::Result<Vec<KeyVersion>> {
+        use lsm_tree::{InternalValue, ValueType};
+
+        let key = key.as_ref();
+
+        // NOTE: Internal keys are sorted by user key, then by descending seqno,
+        // so only the versions of the key are visited, instead of the entire memtable
+        let lo = InternalValue::from_components(key, [], SeqNo::MAX, ValueType::Value).key;
+        let hi = InternalValue::from_components(key, [], 0, ValueType::Value).key;
+
+        // IMPORTANT: Read memtables before segments, so a concurrent flush may only cause duplicates
+        let mut items = self.with_memtables(|active_memtable, sealed_memtables| {
+            std::iter::once(active_memtable)
+                .chain(sealed_memtables.iter().map(|memtable| &**memtable))
+                .flat_map(|memtable| memtable.items.range(lo.clone()..=hi.clone()))
+                .map(|entry| InternalValue::new(entry.key().clone(), entry.value().clone()))
+                .collect::<Vec<_>>()
+        })?;
+
+        let segments = self
+            .index_tree()
+            .levels
+            .read()
+            .expect("lock is poisoned")
+            .iter()
+            .filter(|segment| segment.is_key_in_key_range(key))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for segment in segments {
+            for item in segment.iter() {
+                let item = item?;
+
+                match (*item.key.user_key).cmp(key) {
+                    std::cmp::Ordering::Less => {}
+                    std::cmp::Ordering::Equal => items.push(item),
+                    std::cmp::Ordering::Greater => break,
+                }
+            }
+        }
+
+        items.sort_by_key(|item| std::cmp::Reverse(item.key.seqno));
+        items.dedup_by_key(|item| item.key.seqno);
+
+        let mut versions = Vec::with_capacity(items.len());
+
+        for item in items {
+            let value = if item.is_tombstone() {
+                None
+            } else {
+                match resolve_value(&self.tree, item.value)? {
+                    Some(value) => Some(value),
+
+                    // NOTE: Blob was already garbage collected
+                    None => continue,
+                }
+            };
+
+            versions.push(KeyVersion {
+                seqno: item.key.seqno,
+                value,
+            });
+        }
+
+        Ok(versions)
+    }
+
+    /// Returns every retained write with a sequence number greater than `seqno`
+    /// (or every retained write if `None`), oldest first, including deletions.
+    ///
+    /// Passing the sequence number of the last returned change resumes the feed
+    /// where it left off, which allows streaming changes for replication.
+    ///
+    /// Versions that have been dropped by compaction (because no snapshot
+    /// could see them anymore) are not returned, so only the latest of multiple
+    /// writes to a key may show up. To keep all changes since a checkpoint,
+    /// hold a snapshot while consuming the feed.
+    ///
+    /// For key-value separated partitions, values that have already
+    /// been dropped by blob garbage collection are skipped.
+    ///
+    /// ###### Caution
+    ///
+    /// Writes are applied to the memtable after their sequence number is assigned,
+    /// so a write that is still in progress may show up after a write with a higher
+    /// sequence number. Resuming is only gapless if no writes are in progress
+    /// while the feed is read.
+    ///
+    /// This operation scans every memtable and every segment that contains
+    /// writes after `seqno`, and collects the changes in memory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fjall::{Config, Keyspace, PartitionCreateOptions};
+    /// #
+    /// # let folder = tempfile::tempdir()?;
+    /// # let keyspace = Config::new(folder).open()?;
+    /// # let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+    /// partition.insert("a", "abc")?;
+    ///
+    /// let changes = partition.changes_since(None)?;
+    /// assert_eq!(1, changes.len());
+    /// let checkpoint = changes.last().map(|change| change.seqno);
+    ///
+    /// partition.insert("b", "def")?;
+    /// partition.remove("a")?;
+    ///
+    /// let changes = partition.changes_since(checkpoint)?;
+    /// assert_eq!(2, changes.len());
+    /// assert_eq!(b"b", &*changes[0].key);
+    /// assert_eq!(b"a", &*changes[1].key);
+    /// #
+    /// # Ok::<(), fjall::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if an IO error occurs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a lock is poisoned.
+    pub fn changes_since(&self, seqno: Option<SeqNo>) -> crate::Result<Vec<Change>> {
+        let is_newer = |item_seqno: SeqNo| seqno.map_or(true, |seqno| item_seqno > seqno);
+
+        // IMPORTANT: Read memtables before segments, so a concurrent flush may only cause duplicates
+        let mut items = self.with_memtables(|active_memtable, sealed_memtables| {
+            std::iter::once(active_memtable)
+                .chain(sealed_memtables.iter().map(|memtable| &**memtable))
+                .flat_map(Memtable::iter)
+                .filter(|item| is_newer(item.key.seqno))
+                .collect::<Vec<_>>()
+        })?;
+
+        let segments = self
+            .index_tree()
+            .levels
+            .read()
+            .expect("lock is poisoned")
+            .iter()
+            .filter(|segment| is_newer(segment.metadata.seqnos.1))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for segment in segments {
+            for item in segment.iter() {
+                let item = item?;
+
+                if is_newer(item.key.seqno) {
+                    items.push(item);
+                }
+            }
+        }
+
+        items.sort_by(|a, b| (a.key.seqno, &a.key.user_key).cmp(&(b.key.seqno, &b.key.user_key)));
+        items.dedup_by(|a, b| a.key.seqno == b.key.seqno && a.key.user_key == b.key.user_key);
+
+        let mut changes = Vec::with_capacity(items.len());
+
+        for item in items {
+            let value = if item.is_tombstone() {
+                item.value
+            } else {
+                match resolve_value(&self.tree, item.value)? {
+                    Some(value) => value,
+
+                    // NOTE: Blob was already garbage collected
+                    None => continue,
+                }
+            };
+
+            changes.push(Change {
+                seqno: item.key.seqno,
+                key: item.key.user_key,
+                value_type: item.key.value_type,
+                value,
+            });
+        }
+
+        Ok(changes)
+    }
+
+    /// Checks the disk segments of the partition for corruption and inconsistencies.
+    ///
+    /// Recomputes the checksum of every block, and walks every segment to check
+    /// that its items are sorted and match the key range, seqno range and
+    /// item count stored in the segment metadata.
+    ///
+    /// Problems are collected into the returned report instead of failing early.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fjall::{Config, Keyspace, PartitionCreateOptions};
+    /// #
+    /// # let folder = tempfile::tempdir()?;
+    /// # let keyspace = Config::new(folder).open()?;
+    /// # let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+    /// partition.insert("a", "abc")?;
+    /// partition.rotate_memtable_and_wait()?;
+    ///
+    /// let report = partition.verify()?;
+    /// assert!(report.is_ok());
+    /// assert_eq!(1, report.segment_count);
+    /// #
+    /// # Ok::<(), fjall::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if an IO error occurs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a lock is poisoned.
+    pub fn verify(&self) -> crate::Result<VerifyReport> {
+        let mut report = VerifyReport {
+            corrupted_block_count: self.tree.verify()?,
+            ..Default::default()
+        };
+
+        let segments = self
+            .index_tree()
+            .levels
+            .read()
+            .expect("lock is poisoned")
+            .iter()
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for segment in &segments {
+            report.check_segment(segment);
+        }
+
+        Ok(report)
+    }
+
+    /// Bulk loads sorted key-value pairs into the partition.
+    ///
+    /// The items are written straight into new disk segments, bypassing the journal
+    /// and the active memtable, and all items are assigned the same, current seqno.
+    /// The segments are registered atomically once all items were written, so either
+    /// all or none of the items become visible.
+    ///
+    /// Before the segments are registered, the partition's memtables are flushed,
+    /// so no older unflushed write is covered up by the ingested segments.
+    /// Writes that run concurrently to the ingestion may be ordered before or
+    /// after the ingested items.
+    ///
+    /// The seqno is taken before the segments are written, so snapshots that are
+    /// opened while the ingestion is running will see the ingested items once
+    /// they are registered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fjall::{Config, Keyspace, PartitionCreateOptions};
+    /// #
+    /// # let folder = tempfile::tempdir()?;
+    /// # let keyspace = Config::new(folder).open()?;
+    /// # let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+    /// partition.ingest((0..100u64).map(|x| (x.to_be_bytes(), "abc")))?;
+    ///
+    /// assert_eq!(100, partition.len()?);
+    /// #
+    /// # Ok::<(), fjall::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if an IO error occurs, or [`Error::UnsortedIngestion`]
+    /// if the keys are not strictly ascending, in which case nothing is ingested.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lock is poisoned.
+    pub fn ingest<K: AsRef<[u8]>, V: AsRef<[u8]>>(
+        &self,
+        iter: impl IntoIterator<Item = (K, V)>,
+    ) -> crate::Result<()> {
+        use lsm_tree::{InternalValue, ValueType};
+
+        self.check_ingestion_allowed()?;
+
+        let seqno = self.seqno.next();
+
+        let mut segments = vec![];
+        let mut memtable = Memtable::default();
+        let mut prev_key: Option<UserKey> = None;
+
+        // NOTE: If we bail out early, the segments written so far are not registered,
+        // so they are cleaned up as orphans on the next recovery
+        for (key, value) in iter {
+            let key = key.as_ref();
+
+            if prev_key.as_deref().is_some_and(|prev| prev >= key) {
+                return Err(crate::Error::UnsortedIngestion);
+            }
+            prev_key = Some(key.into());
+
+            let (_, memtable_size) = memtable.insert(InternalValue::from_components(
+                key,
+                value.as_ref(),
+                seqno,
+                ValueType::Value,
+            ));
+
+            if memtable_size >= self.config.max_memtable_size {
+                let full_memtable = Arc::new(std::mem::take(&mut memtable));
+                let segment_id = self.tree.get_next_segment_id();
+                segments.extend(self.tree.flush_memtable(segment_id, &full_memtable, 0)?);
+            }
+        }
+
+        if !memtable.is_empty() {
+            let segment_id = self.tree.get_next_segment_id();
+            segments.extend(
+                self.tree
+                    .flush_memtable(segment_id, &Arc::new(memtable), 0)?,
+            );
+        }
+
+        if segments.is_empty() {
+            return Ok(());
+        }
+
+        log::debug!(
+            "Ingested {} segments into partition {:?} at seqno={seqno}",
+            segments.len(),
+            self.name,
+        );
+
+        // IMPORTANT: Flush older writes first, otherwise the ingested segments
+        // would raise the persisted seqno above unflushed journaled data
+        self.rotate_memtable()?;
+
+        // NOTE: The flush workers are stopped when the keyspace is closed,
+        // so bail out instead of waiting for the sealed memtables forever
+        while !self
+            .flush_manager
+            .read()
+            .expect("lock is poisoned")
+            .sealed_memtables(&self.name)
+            .is_empty()
+        {
+            self.check_ingestion_allowed()?;
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+
+        self.tree.register_segments(&segments)?;
+        self.compaction_manager.notify(self.clone());
+
+        Ok(())
+    }
+
+    /// Returns `Err` if the partition cannot be written to anymore.
+    fn check_ingestion_allowed(&self) -> crate::Result<()> {
+        use std::sync::atomic::Ordering;
+
+        if self.is_deleted.load(Ordering::Relaxed) {
+            return Err(crate::Error::PartitionDeleted);
+        }
+
+        let is_closed = self.journal.try_get_writer()?.is_closed;
+
+        if self.is_poisoned.load(Ordering::Relaxed) {
+            return Err(crate::Error::Poisoned);
+        }
+
+        if is_closed {
+            return Err(crate::Error::KeyspaceClosed);
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if any segment may contain data that can be
+    /// dropped by compaction below the given GC watermark.
+    pub(crate) fn has_reclaimable_data(&self, gc_watermark: crate::Instant) -> bool {
+        let manifest = self.index_tree().levels.read().expect("lock is poisoned");
+
+        let has_reclaimable_data = manifest.iter().any(|segment| {
+            let meta = &segment.metadata;
+            meta.seqnos.0 < gc_watermark
+                && (meta.tombstone_count > 0 || meta.item_count > meta.key_count)
+        });
+
+        has_reclaimable_data
+    }
+
+    /// Opens a snapshot of this partition.
+    #[must_use]
+    pub fn snapshot(&self) -> crate::Snapshot {
+        self.snapshot_at(self.seqno.get())
+    }
+
+    /// Opens a snapshot of this partition with a given sequence number.
+    #[must_use]
+    pub fn snapshot_at(&self, seqno: crate::Instant) -> crate::Snapshot {
         crate::Snapshot::new(
             self.tree.snapshot(seqno),
             SnapshotNonce::new(seqno, self.snapshot_tracker.clone()),
         )
     }
 
+    /// Opens a snapshot of this partition, respecting the configured limit of open snapshots.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fjall::{Config, PartitionCreateOptions};
+    /// #
+    /// # let folder = tempfile::tempdir()?;
+    /// let keyspace = Config::new(folder).max_open_snapshots(1).open()?;
+    /// # let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+    /// let snapshot = partition.try_snapshot()?;
+    /// assert!(partition.try_snapshot().is_err());
+    ///
+    /// drop(snapshot);
+    /// assert!(partition.try_snapshot().is_ok());
+    /// #
+    /// # Ok::<(), fjall::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if too many snapshots are open, see [`crate::Config::max_open_snapshots`].
+    pub fn try_snapshot(&self) -> crate::Result<crate::Snapshot> {
+        self.try_snapshot_at(self.seqno.get())
+    }
+
+    /// Opens a snapshot of this partition with a given sequence number,
+    /// respecting the configured limit of open snapshots.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if too many snapshots are open, see [`crate::Config::max_open_snapshots`].
+    pub fn try_snapshot_at(&self, seqno: crate::Instant) -> crate::Result<crate::Snapshot> {
+        Ok(crate::Snapshot::new(
+            self.tree.snapshot(seqno),
+            SnapshotNonce::try_new(seqno, self.snapshot_tracker.clone())?,
+        ))
+    }
+
     /// Inserts a key-value pair into the partition.
     ///
     /// Keys may be up to 65536 bytes long, values up to 2^32 bytes.
@@ -867,10 +2230,13 @@ impl PartitionHandle {
             return Err(crate::Error::PartitionDeleted);
         }
 
+        self.write_buffer_manager
+            .check_saturation(&self.keyspace_config)?;
+
         let key = key.as_ref();
         let value = value.as_ref();
 
-        let mut journal_writer = self.journal.get_writer();
+        let mut journal_writer = self.journal.try_get_writer()?;
 
         let seqno = self.seqno.next();
 
@@ -879,6 +2245,10 @@ impl PartitionHandle {
             return Err(crate::Error::Poisoned);
         }
 
+        if journal_writer.is_closed {
+            return Err(crate::Error::KeyspaceClosed);
+        }
+
         journal_writer.write_raw(&self.name, key, value, lsm_tree::ValueType::Value, seqno)?;
 
         if !self.config.manual_journal_persist {
@@ -899,6 +2269,7 @@ impl PartitionHandle {
 
         let write_buffer_size = self.write_buffer_manager.allocate(u64::from(item_size));
 
+        self.check_journal_rotation()?;
         self.check_memtable_overflow(memtable_size)?;
 
         self.check_write_buffer_size(write_buffer_size);
@@ -942,9 +2313,12 @@ impl PartitionHandle {
             return Err(crate::Error::PartitionDeleted);
         }
 
+        self.write_buffer_manager
+            .check_saturation(&self.keyspace_config)?;
+
         let key = key.as_ref();
 
-        let mut journal_writer = self.journal.get_writer();
+        let mut journal_writer = self.journal.try_get_writer()?;
 
         let seqno = self.seqno.next();
 
@@ -953,6 +2327,10 @@ impl PartitionHandle {
             return Err(crate::Error::Poisoned);
         }
 
+        if journal_writer.is_closed {
+            return Err(crate::Error::KeyspaceClosed);
+        }
+
         journal_writer.write_raw(&self.name, key, &[], lsm_tree::ValueType::Tombstone, seqno)?;
 
         if !self.config.manual_journal_persist {
@@ -973,9 +2351,200 @@ impl PartitionHandle {
 
         let write_buffer_size = self.write_buffer_manager.allocate(u64::from(item_size));
 
+        self.check_journal_rotation()?;
         self.check_memtable_overflow(memtable_size)?;
         self.check_write_buffer_size(write_buffer_size);
 
         Ok(())
     }
+
+    /// Inserts a key-value pair into the partition and returns the previous value, if any.
+    ///
+    /// The previous value is read while holding the journal lock, and the write
+    /// is applied before the lock is released, so this is atomic with respect to
+    /// other fetching writes. Plain writes that run concurrently may or may not
+    /// be observed as the previous value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fjall::{Config, Keyspace, PartitionCreateOptions};
+    /// #
+    /// # let folder = tempfile::tempdir()?;
+    /// # let keyspace = Config::new(folder).open()?;
+    /// # let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+    /// assert_eq!(None, partition.insert_and_fetch("a", "abc")?);
+    ///
+    /// let prev = partition.insert_and_fetch("a", "def")?;
+    /// assert_eq!(Some("abc".as_bytes().into()), prev);
+    /// #
+    /// # Ok::<(), fjall::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if an IO error occurs.
+    pub fn insert_and_fetch<K: AsRef<[u8]>, V: AsRef<[u8]>>(
+        &self,
+        key: K,
+        value: V,
+    ) -> crate::Result<Option<UserValue>> {
+        let (prev, _) = self.write_and_fetch(
+            key.as_ref(),
+            value.as_ref(),
+            lsm_tree::ValueType::Value,
+            |_| true,
+        )?;
+
+        Ok(prev)
+    }
+
+    /// Removes an item from the partition and returns its previous value, if any.
+    ///
+    /// See [`PartitionHandle::insert_and_fetch`] for the atomicity guarantees.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fjall::{Config, Keyspace, PartitionCreateOptions};
+    /// #
+    /// # let folder = tempfile::tempdir()?;
+    /// # let keyspace = Config::new(folder).open()?;
+    /// # let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+    /// partition.insert("a", "abc")?;
+    ///
+    /// let prev = partition.remove_and_fetch("a")?;
+    /// assert_eq!(Some("abc".as_bytes().into()), prev);
+    ///
+    /// assert_eq!(None, partition.remove_and_fetch("a")?);
+    /// #
+    /// # Ok::<(), fjall::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if an IO error occurs.
+    pub fn remove_and_fetch<K: AsRef<[u8]>>(&self, key: K) -> crate::Result<Option<UserValue>> {
+        let (prev, _) =
+            self.write_and_fetch(key.as_ref(), &[], lsm_tree::ValueType::Tombstone, |_| true)?;
+
+        Ok(prev)
+    }
+
+    /// Atomically replaces the value of a key, if its current value equals `expected`.
+    ///
+    /// `None` as expected value means the key must not exist, `None` as new value
+    /// removes the key. Returns `true` if the swap was applied.
+    ///
+    /// See [`PartitionHandle::insert_and_fetch`] for the atomicity guarantees.
+    /// For multi-key updates, use a transactional keyspace instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fjall::{Config, Keyspace, PartitionCreateOptions};
+    /// #
+    /// # let folder = tempfile::tempdir()?;
+    /// # let keyspace = Config::new(folder).open()?;
+    /// # let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+    /// assert!(partition.compare_and_swap("a", None, Some(b"abc"))?);
+    /// assert!(!partition.compare_and_swap("a", Some(b"def"), Some(b"ghi"))?);
+    /// assert!(partition.compare_and_swap("a", Some(b"abc"), None)?);
+    ///
+    /// assert!(partition.get("a")?.is_none());
+    /// #
+    /// # Ok::<(), fjall::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if an IO error occurs.
+    pub fn compare_and_swap<K: AsRef<[u8]>>(
+        &self,
+        key: K,
+        expected: Option<&[u8]>,
+        new: Option<&[u8]>,
+    ) -> crate::Result<bool> {
+        let (value, value_type) = new.map_or((&[][..], lsm_tree::ValueType::Tombstone), |value| {
+            (value, lsm_tree::ValueType::Value)
+        });
+
+        let (_, swapped) = self.write_and_fetch(key.as_ref(), value, value_type, |prev| {
+            prev.map(|prev| &**prev) == expected
+        })?;
+
+        Ok(swapped)
+    }
+
+    /// Writes a single item, if the predicate holds for the previous value of its key.
+    ///
+    /// Returns the previous value and whether the item was written.
+    fn write_and_fetch(
+        &self,
+        key: &[u8],
+        value: &[u8],
+        value_type: lsm_tree::ValueType,
+        predicate: impl FnOnce(Option<&UserValue>) -> bool,
+    ) -> crate::Result<(Option<UserValue>, bool)> {
+        use std::sync::atomic::Ordering;
+
+        if self.is_deleted.load(Ordering::Relaxed) {
+            return Err(crate::Error::PartitionDeleted);
+        }
+
+        self.write_buffer_manager
+            .check_saturation(&self.keyspace_config)?;
+
+        let mut journal_writer = self.journal.try_get_writer()?;
+
+        // IMPORTANT: Check the poisoned flag after getting journal mutex, otherwise TOCTOU
+        if self.is_poisoned.load(Ordering::Relaxed) {
+            return Err(crate::Error::Poisoned);
+        }
+
+        if journal_writer.is_closed {
+            return Err(crate::Error::KeyspaceClosed);
+        }
+
+        // IMPORTANT: Read while holding the journal lock, so no other fetching write can slip in
+        let prev = self.tree.get(key)?;
+
+        if !predicate(prev.as_ref()) {
+            return Ok((prev, false));
+        }
+
+        let seqno = self.seqno.next();
+
+        journal_writer.write_raw(&self.name, key, value, value_type, seqno)?;
+
+        if !self.config.manual_journal_persist {
+            journal_writer
+                .persist(crate::PersistMode::Buffer)
+                .map_err(|e| {
+                    log::error!(
+                        "persist failed, which is a FATAL, and possibly hardware-related, failure: {e:?}"
+                    );
+                    self.is_poisoned.store(true, Ordering::Relaxed);
+                    e
+                })?;
+        }
+
+        // IMPORTANT: Apply to the memtable before releasing the journal lock,
+        // so the next fetching write sees this write
+        let (item_size, memtable_size) = if value_type == lsm_tree::ValueType::Value {
+            self.tree.insert(key, value, seqno)
+        } else {
+            self.tree.remove(key, seqno)
+        };
+
+        drop(journal_writer);
+
+        let write_buffer_size = self.write_buffer_manager.allocate(u64::from(item_size));
+
+        self.check_journal_rotation()?;
+        self.check_memtable_overflow(memtable_size)?;
+        self.check_write_buffer_size(write_buffer_size);
+
+        Ok((prev, true))
+    }
 }
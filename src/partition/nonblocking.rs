@@ -0,0 +1,93 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+use super::PartitionHandle;
+use crate::io_pool::{spawn_blocking, spawn_iter, IoFuture, IoStream};
+use lsm_tree::{KvPair, UserKey, UserValue};
+use std::ops::{Bound, RangeBounds};
+
+impl PartitionHandle {
+    /// Retrieves an item from the partition on an I/O thread.
+    ///
+    /// See [`PartitionHandle::get`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fjall::{Config, PartitionCreateOptions};
+    /// #
+    /// # let folder = tempfile::tempdir()?;
+    /// # let keyspace = Config::new(folder).open()?;
+    /// # let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+    /// # let run = async {
+    /// partition.insert_async("a", "my_value").await?;
+    ///
+    /// let item = partition.get_async("a").await?;
+    /// assert_eq!(Some("my_value".as_bytes().into()), item);
+    /// # Ok::<(), fjall::Error>(())
+    /// # };
+    /// #
+    /// # Ok::<(), fjall::Error>(())
+    /// ```
+    pub fn get_async<K: AsRef<[u8]>>(&self, key: K) -> IoFuture<crate::Result<Option<UserValue>>> {
+        let partition = self.clone();
+        let key: UserKey = key.as_ref().into();
+
+        spawn_blocking(move || partition.get(key))
+    }
+
+    /// Inserts a key-value pair into the partition on an I/O thread.
+    ///
+    /// See [`PartitionHandle::insert`].
+    pub fn insert_async<K: AsRef<[u8]>, V: AsRef<[u8]>>(
+        &self,
+        key: K,
+        value: V,
+    ) -> IoFuture<crate::Result<()>> {
+        let partition = self.clone();
+        let key: UserKey = key.as_ref().into();
+        let value: UserValue = value.as_ref().into();
+
+        spawn_blocking(move || partition.insert(key, value))
+    }
+
+    /// Removes an item from the partition on an I/O thread.
+    ///
+    /// See [`PartitionHandle::remove`].
+    pub fn remove_async<K: AsRef<[u8]>>(&self, key: K) -> IoFuture<crate::Result<()>> {
+        let partition = self.clone();
+        let key: UserKey = key.as_ref().into();
+
+        spawn_blocking(move || partition.remove(key))
+    }
+
+    /// Returns a stream over a range of items, read on a dedicated thread.
+    ///
+    /// See [`PartitionHandle::range`].
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the thread could not be spawned.
+    pub fn range_async<K: AsRef<[u8]>, R: RangeBounds<K>>(
+        &self,
+        range: R,
+    ) -> crate::Result<IoStream<crate::Result<KvPair>>> {
+        // TODO: Bound::map 1.77
+        let start: Bound<UserKey> = match range.start_bound() {
+            Bound::Included(k) => Bound::Included(k.as_ref().into()),
+            Bound::Excluded(k) => Bound::Excluded(k.as_ref().into()),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+        // TODO: Bound::map 1.77
+        let end: Bound<UserKey> = match range.end_bound() {
+            Bound::Included(k) => Bound::Included(k.as_ref().into()),
+            Bound::Excluded(k) => Bound::Excluded(k.as_ref().into()),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+
+        let partition = self.clone();
+
+        spawn_iter(move || partition.range((start, end))).map_err(Into::into)
+    }
+}
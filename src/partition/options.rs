@@ -332,11 +332,43 @@ impl CreateOptions {
         self
     }
 
+    /// Sets the target false positive rate of bloom filters.
+    ///
+    /// A lower rate uses more memory per key, but avoids more disk reads
+    /// for point reads of keys that do not exist in a segment.
+    ///
+    /// The rate is stored as bits per key in the partition config.
+    ///
+    /// Once set for a partition, this property is not considered in the future.
+    ///
+    /// Default = ~1% (10 bits per key)
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rate is not in the range (0.0, 1.0).
+    #[must_use]
+    pub fn bloom_fp_rate(mut self, rate: f64) -> Self {
+        assert!(rate > 0.0 && rate < 1.0, "invalid bloom filter FP rate");
+
+        // NOTE: Optimal bits per key for a given FP rate = -ln(p) / ln(2)^2
+        let bits = (-rate.ln() / std::f64::consts::LN_2.powi(2)).ceil();
+
+        #[allow(clippy::cast_possible_truncation)]
+        {
+            self.bloom_bits_per_key = bits.min(f64::from(i8::MAX)) as i8;
+        }
+
+        self
+    }
+
     /// Sets the compression method.
     ///
     /// Once set for a partition, this property is not considered in the future.
     ///
     /// Default = In order: Lz4 -> Miniz -> None, depending on compilation flags
+    ///
+    /// Use [`CompressionType::None`] for partitions storing already compressed
+    /// payloads (images, compressed protobuf, ...) to skip the compression pass entirely.
     #[must_use]
     pub fn compression(mut self, compression: CompressionType) -> Self {
         self.compression = compression;
@@ -370,6 +402,10 @@ impl CreateOptions {
 
     /// Sets the maximum memtable size.
     ///
+    /// When a write pushes the partition's active memtable over this size,
+    /// the memtable is rotated and queued for flushing, independent of
+    /// the other partitions and the keyspace's write buffer size.
+    ///
     /// Default = 16 MiB
     ///
     /// Recommended size 8 - 64 MiB, depending on how much memory
@@ -458,6 +494,33 @@ mod tests {
     use super::*;
     use test_log::test;
 
+    #[test]
+    fn partition_opts_bloom_fp_rate() {
+        let c = CreateOptions::default().bloom_fp_rate(0.01);
+        assert_eq!(10, c.bloom_bits_per_key);
+
+        let c = CreateOptions::default().bloom_fp_rate(0.001);
+        assert_eq!(15, c.bloom_bits_per_key);
+
+        let c = CreateOptions::default().bloom_fp_rate(0.5);
+        assert_eq!(2, c.bloom_bits_per_key);
+
+        let c = CreateOptions::default().bloom_fp_rate(f64::MIN_POSITIVE);
+        assert_eq!(i8::MAX, c.bloom_bits_per_key);
+    }
+
+    #[test]
+    #[should_panic = "invalid bloom filter FP rate"]
+    fn partition_opts_bloom_fp_rate_zero() {
+        let _ = CreateOptions::default().bloom_fp_rate(0.0);
+    }
+
+    #[test]
+    #[should_panic = "invalid bloom filter FP rate"]
+    fn partition_opts_bloom_fp_rate_one() {
+        let _ = CreateOptions::default().bloom_fp_rate(1.0);
+    }
+
     #[test]
     #[cfg(not(any(feature = "lz4", feature = "miniz")))]
     fn partition_opts_compression_none() {
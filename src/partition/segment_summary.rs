@@ -0,0 +1,45 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+use lsm_tree::{segment::Segment, SegmentId, SeqNo, UserKey};
+
+/// Read-only summary of a single segment of a partition's LSM-tree
+///
+/// Only built from in-memory segment metadata, so no blocks are read.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[allow(clippy::module_name_repetitions)]
+pub struct SegmentSummary {
+    /// Segment ID
+    pub id: SegmentId,
+
+    /// Level index the segment lives in (0 = first level)
+    pub level: usize,
+
+    /// Smallest and largest key in the segment
+    pub key_range: (UserKey, UserKey),
+
+    /// Lowest and highest sequence number in the segment
+    pub seqnos: (SeqNo, SeqNo),
+
+    /// Number of items (including tombstones) in the segment
+    pub item_count: u64,
+
+    /// On-disk size of the segment, in bytes
+    pub size: u64,
+}
+
+impl SegmentSummary {
+    pub(crate) fn from_segment(level: usize, segment: &Segment) -> Self {
+        let (min, max) = &*segment.metadata.key_range;
+
+        Self {
+            id: segment.metadata.id,
+            level,
+            key_range: (min.clone(), max.clone()),
+            seqnos: segment.metadata.seqnos,
+            item_count: segment.metadata.item_count,
+            size: segment.metadata.file_size,
+        }
+    }
+}
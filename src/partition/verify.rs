@@ -0,0 +1,142 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+use lsm_tree::{segment::Segment, SegmentId};
+
+/// An inconsistency found by [`crate::PartitionHandle::verify`]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[allow(clippy::module_name_repetitions)]
+pub enum VerifyIssue {
+    /// The segment could not be read to the end
+    Unreadable {
+        /// Segment ID
+        segment_id: SegmentId,
+    },
+
+    /// An item is not sorted after its predecessor
+    UnsortedItems {
+        /// Segment ID
+        segment_id: SegmentId,
+
+        /// Position of the first out-of-order item
+        position: u64,
+    },
+
+    /// The first and last key do not match the key range in the segment metadata
+    KeyRangeMismatch {
+        /// Segment ID
+        segment_id: SegmentId,
+    },
+
+    /// The amount of items does not match the item count in the segment metadata
+    ItemCountMismatch {
+        /// Segment ID
+        segment_id: SegmentId,
+
+        /// Item count stored in the segment metadata
+        expected: u64,
+
+        /// Amount of items actually read
+        actual: u64,
+    },
+
+    /// An item's seqno is outside the seqno range in the segment metadata
+    SeqnoOutOfRange {
+        /// Segment ID
+        segment_id: SegmentId,
+    },
+}
+
+/// Result of [`crate::PartitionHandle::verify`]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[allow(clippy::module_name_repetitions)]
+pub struct VerifyReport {
+    /// Amount of segments that were checked
+    pub segment_count: usize,
+
+    /// Amount of items that were checked
+    pub item_count: u64,
+
+    /// Amount of blocks whose checksum does not match their content
+    pub corrupted_block_count: usize,
+
+    /// Inconsistencies found while walking the segments
+    pub issues: Vec<VerifyIssue>,
+}
+
+impl VerifyReport {
+    /// Returns `true` if no corruption or inconsistency was found.
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.corrupted_block_count == 0 && self.issues.is_empty()
+    }
+
+    /// Walks all items of a segment and checks them against its metadata.
+    pub(crate) fn check_segment(&mut self, segment: &Segment) {
+        let meta = &segment.metadata;
+        let segment_id = meta.id;
+
+        let (min_seqno, max_seqno) = meta.seqnos;
+        let (min_key, max_key) = &*meta.key_range;
+
+        self.segment_count += 1;
+
+        let mut item_count = 0;
+        let mut first_key = None;
+        let mut prev_key = None;
+        let mut is_sorted = true;
+        let mut is_seqno_in_range = true;
+
+        for item in segment.iter() {
+            let item = match item {
+                Ok(item) => item,
+                Err(e) => {
+                    log::error!("Segment {segment_id} could not be read: {e:?}");
+                    self.issues.push(VerifyIssue::Unreadable { segment_id });
+                    return;
+                }
+            };
+
+            if is_seqno_in_range && !(min_seqno..=max_seqno).contains(&item.key.seqno) {
+                is_seqno_in_range = false;
+                self.issues
+                    .push(VerifyIssue::SeqnoOutOfRange { segment_id });
+            }
+
+            if let Some(prev_key) = &prev_key {
+                if is_sorted && *prev_key >= item.key {
+                    is_sorted = false;
+                    self.issues.push(VerifyIssue::UnsortedItems {
+                        segment_id,
+                        position: item_count,
+                    });
+                }
+            }
+
+            if first_key.is_none() {
+                first_key = Some(item.key.user_key.clone());
+            }
+
+            prev_key = Some(item.key);
+            item_count += 1;
+        }
+
+        self.item_count += item_count;
+
+        let last_key = prev_key.map(|key| key.user_key);
+
+        if first_key.as_ref() != Some(min_key) || last_key.as_ref() != Some(max_key) {
+            self.issues
+                .push(VerifyIssue::KeyRangeMismatch { segment_id });
+        }
+
+        if item_count != meta.item_count {
+            self.issues.push(VerifyIssue::ItemCountMismatch {
+                segment_id,
+                expected: meta.item_count,
+                actual: item_count,
+            });
+        }
+    }
+}
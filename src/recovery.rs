@@ -8,7 +8,7 @@ use crate::{
     journal::{
         batch_reader::JournalBatchReader, manager::EvictionWatermark, reader::JournalReader,
     },
-    partition::options::CreateOptions as PartitionCreateOptions,
+    partition::{name::is_valid_partition_name, options::CreateOptions as PartitionCreateOptions},
     HashMap, Keyspace, PartitionHandle,
 };
 use lsm_tree::{AbstractTree, AnyTree};
@@ -28,10 +28,16 @@ pub fn recover_partitions(keyspace: &Keyspace) -> crate::Result<()> {
         let partition_name = dirent.file_name();
         let partition_path = dirent.path();
 
-        assert!(
-            dirent.file_type()?.is_dir(),
-            "Found stray file in partitions folder",
-        );
+        if !dirent.file_type()?.is_dir() {
+            log::warn!("Ignoring stray file {partition_name:?} in partitions folder");
+            continue;
+        }
+
+        // NOTE: Skip folders that can not be partitions (e.g. temporary folders)
+        if !partition_name.to_str().is_some_and(is_valid_partition_name) {
+            log::warn!("Ignoring non-partition folder {partition_name:?} in partitions folder");
+            continue;
+        }
 
         log::trace!("Recovering partition {:?}", partition_name);
 
@@ -73,7 +79,7 @@ pub fn recover_partitions(keyspace: &Keyspace) -> crate::Result<()> {
 
         let mut base_config = lsm_tree::Config::new(path)
             .descriptor_table(keyspace.config.descriptor_table.clone())
-            .block_cache(keyspace.config.block_cache.clone())
+            .block_cache(keyspace.config.block_cache_for(partition_name))
             .blob_cache(keyspace.config.blob_cache.clone());
 
         base_config.bloom_bits_per_key = recovered_config.bloom_bits_per_key;
@@ -133,11 +139,11 @@ pub fn recover_sealed_memtables(
         log::debug!("Reading sealed journal at {journal_path:?}");
 
         let raw_reader = JournalReader::new(journal_path)?;
-        let reader = JournalBatchReader::new(raw_reader);
+        let mut reader = JournalBatchReader::new(raw_reader);
 
         let mut watermarks: HashMap<PartitionKey, EvictionWatermark> = HashMap::default();
 
-        for batch in reader {
+        for batch in &mut reader {
             let batch = batch?;
 
             for item in batch.items {
@@ -169,6 +175,12 @@ pub fn recover_sealed_memtables(
             }
         }
 
+        log::debug!(
+            "Recovered {} batches from sealed journal, discarded {} incomplete batches",
+            reader.recovered_count(),
+            reader.discarded_count(),
+        );
+
         log::debug!("Sealing recovered memtables");
         let mut recovered_count = 0;
 
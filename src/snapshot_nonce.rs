@@ -33,4 +33,38 @@ impl SnapshotNonce {
         tracker.open(instant);
         Self { instant, tracker }
     }
+
+    pub fn try_new(instant: Instant, tracker: SnapshotTracker) -> crate::Result<Self> {
+        tracker.try_open(instant)?;
+        Ok(Self { instant, tracker })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    #[test]
+    fn snapshot_nonce_drop_releases_seqno() {
+        let tracker = SnapshotTracker::default();
+
+        let nonce = SnapshotNonce::new(1, tracker.clone());
+
+        // NOTE: Enough short-lived snapshots to trigger GC a couple of times
+        for seqno in 2..=200 {
+            let _ = SnapshotNonce::new(seqno, tracker.clone());
+        }
+
+        // The open snapshot pins its seqno
+        assert_eq!(0, tracker.get_seqno_safe_to_gc());
+
+        drop(nonce);
+
+        for seqno in 201..=300 {
+            let _ = SnapshotNonce::new(seqno, tracker.clone());
+        }
+
+        assert!(tracker.get_seqno_safe_to_gc() > 1);
+    }
 }
@@ -4,7 +4,10 @@
 
 use crate::Instant;
 use dashmap::DashMap;
-use std::sync::{atomic::AtomicU64, Arc, RwLock};
+use std::sync::{
+    atomic::{AtomicU64, AtomicUsize, Ordering},
+    Arc, PoisonError, RwLock,
+};
 
 /// Keeps track of open snapshots
 #[allow(clippy::module_name_repetitions)]
@@ -14,15 +17,35 @@ pub struct SnapshotTrackerInner {
 
     #[doc(hidden)]
     pub(crate) freed_count: AtomicU64,
-    safety_gap: u64,
+
+    /// Distance the GC watermark keeps from the current seqno, see `Config::snapshot_safety_gap`
+    pub(crate) safety_gap: u64,
 
     #[doc(hidden)]
     pub(crate) lowest_freed_instant: RwLock<Instant>,
+
+    /// Amount of currently open snapshots
+    open_count: AtomicUsize,
+
+    /// Upper limit of open snapshots, see `Config::max_open_snapshots`
+    max_open: Option<usize>,
 }
 
 #[derive(Clone, Default)]
 pub struct SnapshotTracker(Arc<SnapshotTrackerInner>);
 
+impl SnapshotTracker {
+    pub fn new(safety_gap: u64, max_open: Option<usize>) -> Self {
+        assert!(safety_gap > 0, "safety gap should be > 0");
+
+        Self(Arc::new(SnapshotTrackerInner {
+            safety_gap,
+            max_open,
+            ..Default::default()
+        }))
+    }
+}
+
 impl std::ops::Deref for SnapshotTracker {
     type Target = SnapshotTrackerInner;
 
@@ -38,12 +61,42 @@ impl Default for SnapshotTrackerInner {
             safety_gap: 50,
             freed_count: AtomicU64::default(),
             lowest_freed_instant: RwLock::default(),
+            open_count: AtomicUsize::default(),
+            max_open: None,
         }
     }
 }
 
 impl SnapshotTrackerInner {
+    /// Opens a snapshot, failing if the configured limit of open snapshots is reached.
+    pub fn try_open(&self, seqno: Instant) -> crate::Result<()> {
+        if let Some(max_open) = self.max_open {
+            self.open_count
+                .fetch_update(Ordering::AcqRel, Ordering::Acquire, |count| {
+                    count.checked_add(1).filter(|&count| count <= max_open)
+                })
+                .map_err(|_| {
+                    log::warn!(
+                        "Cannot open snapshot {seqno}: {max_open} snapshots are already open"
+                    );
+                    crate::Error::SnapshotLimitReached
+                })?;
+        } else {
+            self.open_count.fetch_add(1, Ordering::AcqRel);
+        }
+
+        self.register(seqno);
+
+        Ok(())
+    }
+
+    /// Opens a snapshot, regardless of the limit of open snapshots.
     pub fn open(&self, seqno: Instant) {
+        self.open_count.fetch_add(1, Ordering::AcqRel);
+        self.register(seqno);
+    }
+
+    fn register(&self, seqno: Instant) {
         log::trace!("open snapshot {seqno}");
 
         self.data
@@ -59,6 +112,13 @@ impl SnapshotTrackerInner {
 
         self.data.alter(&seqno, |_, v| v.saturating_sub(1));
 
+        let closed = self
+            .open_count
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |count| {
+                count.checked_sub(1)
+            });
+        debug_assert!(closed.is_ok(), "snapshot was closed more often than opened");
+
         let freed = self
             .freed_count
             .fetch_add(1, std::sync::atomic::Ordering::Relaxed)
@@ -70,13 +130,40 @@ impl SnapshotTrackerInner {
     }
 
     pub fn get_seqno_safe_to_gc(&self) -> Instant {
-        *self.lowest_freed_instant.read().expect("lock is poisoned")
+        // NOTE: The watermark is a plain integer, so it is always consistent,
+        // even if another thread panicked while holding the lock
+        *self
+            .lowest_freed_instant
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Forces a GC pass, advancing the watermark as far as open snapshots allow.
+    ///
+    /// If no snapshots are open, the watermark is pulled up to `current_seqno`,
+    /// minus the safety gap.
+    pub fn gc_now(&self, current_seqno: Instant) -> Instant {
+        self.gc(current_seqno);
+
+        if self.data.is_empty() {
+            let mut lock = self
+                .lowest_freed_instant
+                .write()
+                .unwrap_or_else(PoisonError::into_inner);
+
+            *lock = (*lock).max(current_seqno.saturating_sub(self.safety_gap));
+        }
+
+        self.get_seqno_safe_to_gc()
     }
 
     fn gc(&self, watermark: Instant) {
         log::trace!("snapshot gc, watermark={watermark}");
 
-        let mut lock = self.lowest_freed_instant.write().expect("lock is poisoned");
+        let mut lock = self
+            .lowest_freed_instant
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
 
         let seqno_threshold = watermark.saturating_sub(self.safety_gap);
 
@@ -111,6 +198,55 @@ mod tests {
     use super::*;
     use test_log::test;
 
+    #[test]
+    fn seqno_tracker_max_open() -> crate::Result<()> {
+        let map = SnapshotTracker::new(50, Some(2));
+
+        map.try_open(1)?;
+        map.try_open(2)?;
+        assert!(matches!(
+            map.try_open(3),
+            Err(crate::Error::SnapshotLimitReached)
+        ));
+
+        map.close(1);
+        map.try_open(3)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn seqno_tracker_custom_safety_gap() {
+        let map = SnapshotTracker::new(3, None);
+
+        map.open(1);
+        map.open(2);
+
+        // NOTE: 3rd close triggers GC, but the open snapshot holds back the watermark
+        map.close(2);
+        map.open(3);
+        map.close(3);
+        map.open(4);
+        map.close(4);
+        assert_eq!(map.get_seqno_safe_to_gc(), 0);
+
+        map.close(1);
+
+        for seqno in 5..=10 {
+            map.open(seqno);
+            map.close(seqno);
+        }
+
+        // 9th close runs GC at seqno 9, so everything <= 9 - 3 is freed
+        assert_eq!(map.get_seqno_safe_to_gc(), 6);
+    }
+
+    #[test]
+    #[should_panic = "safety gap should be > 0"]
+    fn seqno_tracker_zero_safety_gap() {
+        let _ = SnapshotTracker::new(0, None);
+    }
+
     #[test]
     #[allow(clippy::field_reassign_with_default)]
     fn seqno_tracker_one_shot() {
@@ -16,7 +16,19 @@ enum Read {
     All,
 }
 
-#[derive(Default, Debug)]
+impl Read {
+    fn contains(&self, key: &Slice) -> bool {
+        match self {
+            Self::Single(k) => k == key,
+            Self::Range { start, end } => {
+                RangeBounds::<Slice>::contains(&(start.as_ref(), end.as_ref()), key)
+            }
+            Self::All => true,
+        }
+    }
+}
+
+#[derive(Clone, Default, Debug)]
 pub struct ConflictManager {
     reads: BTreeMap<PartitionKey, Vec<Read>>,
     conflict_keys: BTreeMap<PartitionKey, BTreeSet<Slice>>,
@@ -46,6 +58,14 @@ impl ConflictManager {
         }
     }
 
+    /// Restores the written keys of an earlier state.
+    ///
+    /// Reads are kept, because values that were read after that state
+    /// may still influence writes made after rolling back.
+    pub fn rollback_writes_to(&mut self, other: &Self) {
+        self.conflict_keys = other.conflict_keys.clone();
+    }
+
     pub fn mark_range(&mut self, partition: &PartitionKey, range: impl RangeBounds<Slice>) {
         let start = match range.start_bound() {
             Bound::Included(k) => Bound::Included(k.clone()),
@@ -180,4 +200,121 @@ impl ConflictManager {
 
         false
     }
+
+    /// Returns the keys written by `other` that overlap the reads of this transaction.
+    ///
+    /// Unlike [`ConflictManager::has_conflict`], this visits every written key,
+    /// so it should only be used once a conflict was detected.
+    pub fn conflicting_keys(&self, other: &Self) -> Vec<(PartitionKey, Slice)> {
+        let mut conflicting_keys = vec![];
+
+        for (partition, reads) in &self.reads {
+            let Some(other_conflict_keys) = other.conflict_keys.get(partition) else {
+                continue;
+            };
+
+            conflicting_keys.extend(
+                other_conflict_keys
+                    .iter()
+                    .filter(|key| reads.iter().any(|read| read.contains(key)))
+                    .map(|key| (partition.clone(), key.clone())),
+            );
+        }
+
+        conflicting_keys
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    #[test]
+    fn conflict_manager_point_reads() {
+        let partition: PartitionKey = "default".into();
+
+        let mut reader = ConflictManager::default();
+        reader.mark_read(&partition, &Slice::from(*b"a"));
+
+        let mut writer = ConflictManager::default();
+        writer.mark_conflict(&partition, b"b");
+        assert!(!reader.has_conflict(&writer));
+
+        writer.mark_conflict(&partition, b"a");
+        assert!(reader.has_conflict(&writer));
+    }
+
+    #[test]
+    fn conflict_manager_conflicting_keys() {
+        let partition: PartitionKey = "default".into();
+
+        let mut reader = ConflictManager::default();
+        reader.mark_read(&partition, &Slice::from(*b"a"));
+        reader.mark_range(&partition, Slice::from(*b"c")..Slice::from(*b"e"));
+
+        let mut writer = ConflictManager::default();
+        writer.mark_conflict(&partition, b"a");
+        writer.mark_conflict(&partition, b"b");
+        writer.mark_conflict(&partition, b"d");
+        writer.mark_conflict(&partition, b"e");
+        writer.mark_conflict(&"other".into(), b"a");
+
+        assert_eq!(
+            vec![
+                (partition.clone(), Slice::from(*b"a")),
+                (partition.clone(), Slice::from(*b"d")),
+            ],
+            reader.conflicting_keys(&writer),
+        );
+
+        let writer = ConflictManager::default();
+        assert!(reader.conflicting_keys(&writer).is_empty());
+    }
+
+    #[test]
+    fn conflict_manager_disjoint_ranges() {
+        let partition: PartitionKey = "default".into();
+
+        let mut reader = ConflictManager::default();
+        reader.mark_range(&partition, Slice::from(*b"b")..Slice::from(*b"d"));
+        reader.mark_range(&partition, Slice::from(*b"x")..);
+
+        let mut writer = ConflictManager::default();
+        writer.mark_conflict(&partition, b"a");
+        writer.mark_conflict(&partition, b"d");
+        writer.mark_conflict(&partition, b"w");
+        assert!(!reader.has_conflict(&writer));
+
+        // NOTE: Writes to other partitions never conflict
+        let mut writer = ConflictManager::default();
+        writer.mark_conflict(&"other".into(), b"c");
+        assert!(!reader.has_conflict(&writer));
+    }
+
+    #[test]
+    fn conflict_manager_overlapping_ranges() {
+        let partition: PartitionKey = "default".into();
+
+        let mut reader = ConflictManager::default();
+        reader.mark_range(&partition, Slice::from(*b"b")..=Slice::from(*b"d"));
+
+        let mut writer = ConflictManager::default();
+        writer.mark_conflict(&partition, b"d");
+        assert!(reader.has_conflict(&writer));
+
+        let mut reader = ConflictManager::default();
+        reader.mark_range(&partition, ..Slice::from(*b"c"));
+
+        let mut writer = ConflictManager::default();
+        writer.mark_conflict(&partition, b"a");
+        assert!(reader.has_conflict(&writer));
+
+        let mut reader = ConflictManager::default();
+        reader.mark_range(&partition, ..);
+
+        let mut writer = ConflictManager::default();
+        writer.mark_conflict(&partition, b"z");
+        assert!(reader.has_conflict(&writer));
+    }
 }
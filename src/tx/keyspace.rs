@@ -10,7 +10,10 @@ use crate::{
 use std::sync::{Arc, Mutex};
 
 #[cfg(feature = "ssi_tx")]
-use super::oracle::Oracle;
+use super::{oracle::Oracle, write::ssi::Conflict};
+
+#[cfg(feature = "ssi_tx")]
+use std::sync::Condvar;
 
 /// Transactional keyspace
 #[derive(Clone)]
@@ -63,14 +66,7 @@ impl TxKeyspace {
     /// Will return `Err` if creation failed.
     #[cfg(feature = "ssi_tx")]
     pub fn write_tx(&self) -> crate::Result<WriteTransaction> {
-        let instant = {
-            // acquire a lock here to prevent getting a stale snapshot seqno
-            // this will drain at least part of the commit queue, but ordering
-            // is platform-dependent since we use std::sync::Mutex
-            let _guard = self.oracle.write_serialize_lock()?;
-
-            self.inner.instant()
-        };
+        let instant = self.oracle.instant()?;
 
         let mut write_tx = WriteTransaction::new(
             self.clone(),
@@ -84,6 +80,78 @@ impl TxKeyspace {
         Ok(write_tx)
     }
 
+    /// Runs `f` inside a write transaction and commits it,
+    /// retrying with a fresh transaction if the commit conflicts.
+    ///
+    /// The transaction is retried up to `max_attempts` times. If `backoff` is set,
+    /// the caller sleeps between attempts, doubling the duration after every conflict.
+    ///
+    /// If `f` returns an error, the transaction is rolled back and the error is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fjall::{Config, PartitionCreateOptions};
+    /// #
+    /// # let folder = tempfile::tempdir()?;
+    /// # let keyspace = Config::new(folder).open_transactional()?;
+    /// # let counters = keyspace.open_partition("counters", PartitionCreateOptions::default())?;
+    /// let new_count = keyspace.transaction(10, None, |tx| {
+    ///     let count = tx
+    ///         .get(&counters, "hits")?
+    ///         .map_or(0, |v| u64::from_be_bytes(v.as_ref().try_into().expect("should be u64")));
+    ///
+    ///     tx.insert(&counters, "hits", (count + 1).to_be_bytes());
+    ///
+    ///     Ok(count + 1)
+    /// })?;
+    ///
+    /// assert_eq!(Ok(1), new_count);
+    /// #
+    /// # Ok::<(), fjall::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if an IO error occurs, or `f` fails.
+    ///
+    /// Returns `Ok(Err(Conflict))` if every attempt conflicted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_attempts` is 0.
+    #[cfg(feature = "ssi_tx")]
+    pub fn transaction<T>(
+        &self,
+        max_attempts: u32,
+        backoff: Option<std::time::Duration>,
+        mut f: impl FnMut(&mut WriteTransaction) -> crate::Result<T>,
+    ) -> crate::Result<Result<T, Conflict>> {
+        assert!(max_attempts > 0, "max_attempts should be > 0");
+
+        let mut backoff = backoff;
+
+        for attempt in 1..=max_attempts {
+            let mut tx = self.write_tx()?;
+            let value = f(&mut tx)?;
+
+            if tx.commit()?.is_ok() {
+                return Ok(Ok(value));
+            }
+
+            log::debug!("transaction conflicted (attempt {attempt}/{max_attempts})");
+
+            if attempt < max_attempts {
+                if let Some(duration) = &mut backoff {
+                    std::thread::sleep(*duration);
+                    *duration = duration.saturating_mul(2);
+                }
+            }
+        }
+
+        Ok(Err(Conflict))
+    }
+
     /// Starts a new read-only transaction.
     #[must_use]
     pub fn read_tx(&self) -> ReadTransaction {
@@ -95,6 +163,61 @@ impl TxKeyspace {
         ))
     }
 
+    /// Returns the current instant, see [`Keyspace::instant`].
+    #[must_use]
+    pub fn instant(&self) -> crate::Instant {
+        self.inner.instant()
+    }
+
+    /// Starts a new read-only transaction that reads the keyspace
+    /// as it was at the given instant.
+    ///
+    /// The instant needs to be at or above the GC watermark, because older
+    /// versions may already have been dropped by compaction.
+    /// Opening a transaction at an instant pins it, like any other snapshot.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fjall::{Config, PartitionCreateOptions};
+    /// #
+    /// # let folder = tempfile::tempdir()?;
+    /// # let keyspace = Config::new(folder).open_transactional()?;
+    /// # let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+    /// partition.insert("a", "old")?;
+    /// let instant = keyspace.instant();
+    /// partition.insert("a", "new")?;
+    ///
+    /// let tx = keyspace.read_tx_at(instant)?;
+    /// assert_eq!(Some("old".as_bytes().into()), tx.get(&partition, "a")?);
+    /// #
+    /// # Ok::<(), fjall::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return [`crate::Error::SnapshotTooOld`] if the instant is below the GC watermark.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the instant is in the future.
+    pub fn read_tx_at(&self, instant: crate::Instant) -> crate::Result<ReadTransaction> {
+        assert!(
+            instant <= self.inner.instant(),
+            "instant should not be in the future"
+        );
+
+        // IMPORTANT: Register the snapshot before checking, so the
+        // GC watermark cannot move past it after the check
+        let nonce = SnapshotNonce::new(instant, self.inner.snapshot_tracker.clone());
+
+        if instant < self.inner.snapshot_tracker.get_seqno_safe_to_gc() {
+            return Err(crate::Error::SnapshotTooOld);
+        }
+
+        Ok(ReadTransaction::new(nonce))
+    }
+
     /// Flushes the active journal. The durability depends on the [`PersistMode`]
     /// used.
     ///
@@ -123,11 +246,22 @@ impl TxKeyspace {
         self.inner.persist(mode)
     }
 
+    /// Flushes all memtables into segments and shuts the keyspace down.
+    ///
+    /// See [`Keyspace::close`].
+    ///
+    /// # Errors
+    ///
+    /// Returns error, if a memtable could not be flushed, or an IO error occurred.
+    pub fn close(self) -> crate::Result<()> {
+        self.inner.close()
+    }
+
     /// Creates or opens a keyspace partition.
     ///
     /// # Errors
     ///
-    /// Returns error, if an IO error occurred.
+    /// Returns error, if an IO error occurred, or if the partition has a reverse index.
     ///
     /// # Panics
     ///
@@ -203,8 +337,10 @@ impl TxKeyspace {
             #[cfg(feature = "ssi_tx")]
             oracle: Arc::new(Oracle {
                 write_serialize_lock: Mutex::default(),
+                pending_finished: Condvar::default(),
                 seqno: inner.seqno.clone(),
                 snapshot_tracker: inner.snapshot_tracker.clone(),
+                max_committed_txns: inner.config.max_committed_txns,
             }),
             inner,
             #[cfg(feature = "single_writer_tx")]
@@ -1,25 +1,96 @@
+use crate::batch::PartitionKey;
 use crate::snapshot_tracker::SnapshotTracker;
 use crate::Instant;
 
 use super::conflict_manager::ConflictManager;
-use lsm_tree::SequenceNumberCounter;
+use lsm_tree::{SequenceNumberCounter, UserKey};
 use std::collections::BTreeMap;
 use std::fmt;
-use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
 
 pub enum CommitOutcome<E> {
     Ok,
     Aborted(E),
-    Conflicted,
+
+    /// Contains the written keys of other transactions that
+    /// overlap the reads of the transaction
+    Conflicted(Vec<(PartitionKey, UserKey)>),
+}
+
+/// Transaction that passed conflict detection and is being written
+struct PendingTxn {
+    /// Seqno counter when the transaction passed conflict detection,
+    /// its writes get a seqno at or above it
+    seqno_floor: Instant,
+
+    conflict_checker: ConflictManager,
+}
+
+/// Transactions that committed while an older snapshot was still open
+#[derive(Default)]
+pub struct CommittedTxns {
+    /// Conflict checkers of committed transactions, keyed by commit seqno
+    /// (and a unique ID, because commits that finish together can share a seqno)
+    txns: BTreeMap<(u64, u64), ConflictManager>,
+
+    /// Transactions that are being written, keyed by ID
+    pending: BTreeMap<u64, PendingTxn>,
+
+    next_id: u64,
+
+    /// Highest commit seqno that was dropped because of the limit
+    ///
+    /// Transactions that started before it cannot be checked for conflicts anymore.
+    evicted_watermark: Option<u64>,
+}
+
+impl CommittedTxns {
+    #[cfg(test)]
+    pub fn len(&self) -> usize {
+        self.txns.len()
+    }
+}
+
+/// Drops a pending transaction if it was not recorded as committed,
+/// e.g. because writing it panicked, and wakes up new transactions waiting for it
+struct PendingGuard<'a> {
+    oracle: &'a Oracle,
+    id: u64,
+}
+
+impl Drop for PendingGuard<'_> {
+    fn drop(&mut self) {
+        self.oracle
+            .write_serialize_lock
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .pending
+            .remove(&self.id);
+
+        self.oracle.pending_finished.notify_all();
+    }
 }
 
 pub struct Oracle {
-    pub(super) write_serialize_lock: Mutex<BTreeMap<u64, ConflictManager>>,
+    pub(super) write_serialize_lock: Mutex<CommittedTxns>,
+
+    /// Notified whenever a pending transaction finished, see [`Oracle::instant`]
+    pub(super) pending_finished: Condvar,
+
     pub(super) seqno: SequenceNumberCounter,
     pub(super) snapshot_tracker: SnapshotTracker,
+    pub(super) max_committed_txns: Option<usize>,
 }
 
 impl Oracle {
+    /// Checks the transaction for conflicts, and if there are none, writes it using `f`.
+    ///
+    /// The write serialize lock is only held for conflict detection and for
+    /// recording the committed transaction, not while `f` runs, so a slow write
+    /// (e.g. one that fsyncs the journal) does not hold up other commits.
+    /// While `f` runs, the transaction is pending: other transactions
+    /// are checked against it, and new transactions wait for it if it
+    /// may already be partially visible (see [`Oracle::instant`]).
     #[allow(clippy::nursery)]
     pub(super) fn with_commit<E, F: FnOnce() -> Result<(), E>>(
         &self,
@@ -27,10 +98,7 @@ impl Oracle {
         conflict_checker: ConflictManager,
         f: F,
     ) -> crate::Result<CommitOutcome<E>> {
-        let mut committed_txns = self
-            .write_serialize_lock
-            .lock()
-            .map_err(|_| crate::Error::Poisoned)?;
+        let mut committed_txns = self.write_serialize_lock()?;
 
         // If the committed_txn.ts is less than Instant that implies that the
         // committed_txn finished before the current transaction started.
@@ -40,31 +108,121 @@ impl Oracle {
         // a txn before it.
         let conflicted =
             committed_txns
-                .range((instant + 1)..)
+                .txns
+                .range((instant + 1, 0)..)
                 .any(|(_ts, other_conflict_checker)| {
                     conflict_checker.has_conflict(other_conflict_checker)
                 });
 
-        self.snapshot_tracker.close(instant);
+        // NOTE: Pending transactions have not finished yet, so they
+        // finish after the current transaction started
+        let conflicted = conflicted
+            || committed_txns
+                .pending
+                .values()
+                .any(|other| conflict_checker.has_conflict(&other.conflict_checker));
+
+        // NOTE: If a transaction that committed after we started was dropped,
+        // we cannot tell if it conflicts, so assume it does
+        let conflicted = conflicted
+            || committed_txns
+                .evicted_watermark
+                .is_some_and(|watermark| watermark > instant);
+
+        // NOTE: The transaction's snapshot is still open, it is closed by its nonce
         let safe_to_gc = self.snapshot_tracker.get_seqno_safe_to_gc();
-        committed_txns.retain(|ts, _| *ts > safe_to_gc);
+        committed_txns.txns.retain(|(ts, _), _| *ts > safe_to_gc);
 
         if conflicted {
-            return Ok(CommitOutcome::Conflicted);
+            // NOTE: Only collect the keys once we know there is a conflict,
+            // so the common path can stop at the first match
+            let mut keys = committed_txns
+                .txns
+                .range((instant + 1, 0)..)
+                .map(|(_ts, other)| other)
+                .chain(committed_txns.pending.values().map(|p| &p.conflict_checker))
+                .flat_map(|other| conflict_checker.conflicting_keys(other))
+                .collect::<Vec<_>>();
+
+            keys.sort();
+            keys.dedup();
+
+            return Ok(CommitOutcome::Conflicted(keys));
         }
 
-        if let Err(e) = f() {
+        let id = committed_txns.next_id;
+        committed_txns.next_id += 1;
+
+        committed_txns.pending.insert(
+            id,
+            PendingTxn {
+                seqno_floor: self.seqno.get(),
+                conflict_checker,
+            },
+        );
+
+        drop(committed_txns);
+
+        // NOTE: Declared before the lock below, so it is dropped after the lock is released
+        let _guard = PendingGuard { oracle: self, id };
+        let result = f();
+
+        let mut committed_txns = self.write_serialize_lock()?;
+
+        let Some(pending) = committed_txns.pending.remove(&id) else {
+            unreachable!("pending transaction should exist");
+        };
+
+        if let Err(e) = result {
             return Ok(CommitOutcome::Aborted(e));
         }
 
-        committed_txns.insert(self.seqno.get(), conflict_checker);
+        // NOTE: Every seqno of the transaction is below the current seqno
+        committed_txns
+            .txns
+            .insert((self.seqno.get(), id), pending.conflict_checker);
+
+        if let Some(limit) = self.max_committed_txns {
+            while committed_txns.txns.len() > limit {
+                let Some(((ts, _), _)) = committed_txns.txns.pop_first() else {
+                    break;
+                };
+                committed_txns.evicted_watermark = Some(ts);
+            }
+        }
 
         Ok(CommitOutcome::Ok)
     }
 
-    pub(super) fn write_serialize_lock(
-        &self,
-    ) -> crate::Result<MutexGuard<BTreeMap<u64, ConflictManager>>> {
+    /// Returns the snapshot seqno for a new write transaction.
+    ///
+    /// A pending transaction that started before the current seqno may have written
+    /// below it without being visible yet, so this waits for such transactions to
+    /// finish, otherwise the transaction's reads could change once they are applied.
+    /// Transactions that are still pending afterwards write above the returned seqno,
+    /// so they are invisible to the new transaction, and it is checked against them.
+    pub(super) fn instant(&self) -> crate::Result<Instant> {
+        let mut committed_txns = self.write_serialize_lock()?;
+
+        let seqno = self.seqno.get();
+
+        while committed_txns
+            .pending
+            .values()
+            .any(|pending| pending.seqno_floor < seqno)
+        {
+            committed_txns = self
+                .pending_finished
+                .wait(committed_txns)
+                .map_err(|_| crate::Error::Poisoned)?;
+        }
+
+        drop(committed_txns);
+
+        Ok(seqno)
+    }
+
+    pub(super) fn write_serialize_lock(&self) -> crate::Result<MutexGuard<CommittedTxns>> {
         self.write_serialize_lock
             .lock()
             .map_err(|_| crate::Error::Poisoned)
@@ -98,6 +256,203 @@ mod tests {
         Ok(())
     }
 
+    #[allow(clippy::unwrap_used)]
+    #[test]
+    fn oracle_committed_txns_limit() -> crate::Result<()> {
+        let tmpdir = tempfile::tempdir()?;
+        let ks = Config::new(tmpdir.path())
+            .max_committed_txns(100)
+            .open_transactional()?;
+
+        let part = ks.open_partition("foo", PartitionCreateOptions::default())?;
+        part.insert("seed", "abc")?;
+
+        // NOTE: Pins the GC watermark, so committed transactions cannot be pruned
+        let snapshot = ks.read_tx();
+
+        let mut old_tx = ks.write_tx()?;
+        old_tx.insert(&part, "untouched", "abc");
+
+        for x in 0..2_000u64 {
+            let mut tx = ks.write_tx()?;
+            tx.insert(&part, x.to_be_bytes(), "abc");
+            tx.commit()?.unwrap();
+
+            assert!(ks.oracle.write_serialize_lock.lock().unwrap().len() <= 100);
+        }
+
+        // NOTE: Transactions that committed after old_tx started were dropped,
+        // so old_tx cannot be checked anymore, even though it does not conflict
+        assert!(old_tx.commit()?.is_err());
+
+        let mut tx = ks.write_tx()?;
+        tx.insert(&part, "untouched", "abc");
+        tx.commit()?.unwrap();
+
+        assert_eq!(1, snapshot.len(&part)?);
+
+        Ok(())
+    }
+
+    #[allow(clippy::unwrap_used)]
+    #[test]
+    fn oracle_slow_commit_does_not_block() -> crate::Result<()> {
+        use super::{CommitOutcome, ConflictManager};
+        use std::{sync::mpsc, time::Duration};
+
+        let tmpdir = tempfile::tempdir()?;
+        let ks = Config::new(tmpdir.path()).open_transactional()?;
+
+        let part = ks.open_partition("foo", PartitionCreateOptions::default())?;
+        part.insert("seed", "abc")?;
+
+        let (entered_tx, entered_rx) = mpsc::channel();
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+
+        let slow_commit = std::thread::spawn({
+            let ks = ks.clone();
+
+            move || -> crate::Result<bool> {
+                let instant = ks.oracle.instant()?;
+
+                let mut conflict_checker = ConflictManager::default();
+                conflict_checker.mark_conflict(&"foo".into(), b"slow");
+
+                let outcome = ks.oracle.with_commit(instant, conflict_checker, || {
+                    entered_tx.send(()).unwrap();
+
+                    release_rx
+                        .recv_timeout(Duration::from_secs(10))
+                        .map_err(|_| "commit was blocked")
+                })?;
+
+                Ok(matches!(outcome, CommitOutcome::Ok))
+            }
+        });
+
+        entered_rx.recv().unwrap();
+
+        // NOTE: Started while the slow commit is pending, so it has to be checked against it
+        let mut reader = ks.write_tx()?;
+        assert!(reader.get(&part, "slow")?.is_none());
+        reader.insert(&part, "other", "abc");
+
+        let mut tx = ks.write_tx()?;
+        tx.insert(&part, "fast", "abc");
+        tx.commit()?.unwrap();
+
+        release_tx.send(()).unwrap();
+        assert!(slow_commit.join().unwrap()?);
+
+        assert!(reader.commit()?.is_err());
+
+        let mut tx = ks.write_tx()?;
+        assert!(tx.get(&part, "fast")?.is_some());
+        tx.insert(&part, "slow", "abc");
+        tx.commit()?.unwrap();
+
+        Ok(())
+    }
+
+    #[allow(clippy::unwrap_used)]
+    #[test]
+    fn oracle_new_tx_sees_commit_while_other_is_pending() -> crate::Result<()> {
+        use super::{CommitOutcome, ConflictManager};
+        use std::{sync::mpsc, time::Duration};
+
+        let tmpdir = tempfile::tempdir()?;
+        let ks = Config::new(tmpdir.path()).open_transactional()?;
+
+        let part = ks.open_partition("foo", PartitionCreateOptions::default())?;
+        part.insert("seed", "abc")?;
+
+        let (entered_tx, entered_rx) = mpsc::channel();
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+
+        let slow_commit = std::thread::spawn({
+            let ks = ks.clone();
+
+            move || -> crate::Result<bool> {
+                let instant = ks.oracle.instant()?;
+
+                let mut conflict_checker = ConflictManager::default();
+                conflict_checker.mark_conflict(&"foo".into(), b"slow");
+
+                let outcome = ks.oracle.with_commit(instant, conflict_checker, || {
+                    entered_tx.send(()).unwrap();
+
+                    release_rx
+                        .recv_timeout(Duration::from_secs(10))
+                        .map_err(|_| "commit was never released")
+                })?;
+
+                Ok(matches!(outcome, CommitOutcome::Ok))
+            }
+        });
+
+        entered_rx.recv().unwrap();
+
+        let mut tx = ks.write_tx()?;
+        tx.insert(&part, "a", "abc");
+        tx.commit()?.unwrap();
+
+        let releaser = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(100));
+            release_tx.send(()).unwrap();
+        });
+
+        // NOTE: Opened while the slow commit is pending, but it
+        // sees the earlier commit, and does not conflict with it
+        let mut tx = ks.write_tx()?;
+        assert!(tx.get(&part, "a")?.is_some());
+        tx.insert(&part, "b", "abc");
+        tx.commit()?.unwrap();
+
+        releaser.join().unwrap();
+        assert!(slow_commit.join().unwrap()?);
+
+        Ok(())
+    }
+
+    #[allow(clippy::unwrap_used)]
+    #[test]
+    fn oracle_poisoned_commit_drops_pending() -> crate::Result<()> {
+        use super::ConflictManager;
+        use std::sync::PoisonError;
+
+        let tmpdir = tempfile::tempdir()?;
+        let ks = Config::new(tmpdir.path()).open_transactional()?;
+
+        let instant = ks.oracle.instant()?;
+
+        let result = ks
+            .oracle
+            .with_commit(instant, ConflictManager::default(), || {
+                // NOTE: Poison the lock while the transaction is pending
+                std::thread::scope(|s| {
+                    s.spawn(|| {
+                        let _lock = ks.oracle.write_serialize_lock.lock().unwrap();
+                        panic!("poison");
+                    })
+                    .join()
+                })
+                .map_err(|_| ())
+            });
+
+        assert!(matches!(result, Err(crate::Error::Poisoned)));
+
+        assert!(ks.oracle.write_serialize_lock.is_poisoned());
+
+        let committed_txns = ks
+            .oracle
+            .write_serialize_lock
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        assert!(committed_txns.pending.is_empty());
+
+        Ok(())
+    }
+
     fn run_tx(ks: &TxKeyspace, part: &TxPartitionHandle) -> Result<(), Box<dyn std::error::Error>> {
         let mut tx1 = ks.write_tx()?;
         let mut tx2 = ks.write_tx()?;
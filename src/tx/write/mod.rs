@@ -24,6 +24,17 @@ fn ignore_tombstone_value(item: InternalValue) -> Option<InternalValue> {
     }
 }
 
+/// A point inside a write transaction that can be rolled back to
+///
+/// See [`crate::WriteTransaction::savepoint`].
+#[must_use]
+pub struct Savepoint {
+    memtables: HashMap<PartitionKey, Arc<Memtable>>,
+
+    #[cfg(feature = "ssi_tx")]
+    pub(super) cm: crate::tx::conflict_manager::ConflictManager,
+}
+
 /// A single-writer (serialized) cross-partition transaction
 ///
 /// Use [`WriteTransaction::commit`] to commit changes to the keyspace.
@@ -391,6 +402,41 @@ impl BaseTransaction {
             ));
     }
 
+    /// Deep copies a write set, because memtables are append-only.
+    fn copy_write_set(
+        memtables: &HashMap<PartitionKey, Arc<Memtable>>,
+    ) -> HashMap<PartitionKey, Arc<Memtable>> {
+        memtables
+            .iter()
+            .map(|(partition_key, memtable)| {
+                let copy = Memtable::default();
+
+                for item in memtable.iter() {
+                    copy.insert(item);
+                }
+
+                (partition_key.clone(), Arc::new(copy))
+            })
+            .collect()
+    }
+
+    /// Copies the pending write set, so it can be restored later.
+    pub(super) fn savepoint(&self) -> Savepoint {
+        Savepoint {
+            memtables: Self::copy_write_set(&self.memtables),
+
+            #[cfg(feature = "ssi_tx")]
+            cm: crate::tx::conflict_manager::ConflictManager::default(),
+        }
+    }
+
+    /// Restores the pending write set of a savepoint.
+    ///
+    /// The savepoint stays intact, so it can be rolled back to multiple times.
+    pub(super) fn rollback_to(&mut self, savepoint: &Savepoint) {
+        self.memtables = Self::copy_write_set(&savepoint.memtables);
+    }
+
     /// Commits the transaction.
     ///
     /// # Errors
@@ -1,4 +1,4 @@
-use super::BaseTransaction as InnerWriteTransaction;
+use super::{BaseTransaction as InnerWriteTransaction, Savepoint};
 use crate::{snapshot_nonce::SnapshotNonce, PersistMode, TxKeyspace, TxPartitionHandle};
 use lsm_tree::{KvPair, UserKey, UserValue};
 use std::{ops::RangeBounds, sync::MutexGuard};
@@ -602,6 +602,41 @@ impl<'a> WriteTransaction<'a> {
         self.inner.remove(partition, key);
     }
 
+    /// Creates a savepoint that the transaction can later be rolled back to,
+    /// without aborting the whole transaction.
+    ///
+    /// Savepoints can be nested: rolling back to a savepoint discards all writes
+    /// made after it, including writes covered by savepoints created later.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fjall::{Config, Keyspace, PartitionCreateOptions};
+    /// #
+    /// # let folder = tempfile::tempdir()?;
+    /// # let keyspace = Config::new(folder).open_transactional()?;
+    /// # let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+    /// let mut tx = keyspace.write_tx();
+    /// tx.insert(&partition, "a", "abc");
+    ///
+    /// let savepoint = tx.savepoint();
+    /// tx.insert(&partition, "b", "abc");
+    /// tx.rollback_to(&savepoint);
+    ///
+    /// assert!(tx.contains_key(&partition, "a")?);
+    /// assert!(!tx.contains_key(&partition, "b")?);
+    /// #
+    /// # Ok::<(), fjall::Error>(())
+    /// ```
+    pub fn savepoint(&self) -> Savepoint {
+        self.inner.savepoint()
+    }
+
+    /// Rolls back all writes made after the given savepoint.
+    pub fn rollback_to(&mut self, savepoint: &Savepoint) {
+        self.inner.rollback_to(savepoint);
+    }
+
     /// Commits the transaction.
     ///
     /// # Errors
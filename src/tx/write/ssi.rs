@@ -1,5 +1,6 @@
-use super::BaseTransaction;
+use super::{BaseTransaction, Savepoint};
 use crate::{
+    batch::PartitionKey,
     snapshot_nonce::SnapshotNonce,
     tx::{conflict_manager::ConflictManager, oracle::CommitOutcome},
     PersistMode, TxKeyspace, TxPartitionHandle,
@@ -10,7 +11,9 @@ use std::{
     ops::{Bound, RangeBounds, RangeFull},
 };
 
-#[derive(Debug)]
+/// Error returned when a transaction could not be committed
+/// because it conflicted with another, concurrently committed transaction
+#[derive(Debug, Eq, PartialEq)]
 pub struct Conflict;
 
 impl std::error::Error for Conflict {}
@@ -21,6 +24,39 @@ impl fmt::Display for Conflict {
     }
 }
 
+/// Error returned by [`WriteTransaction::commit_with_details`] when a transaction
+/// could not be committed, listing the keys it conflicted on
+#[derive(Debug, Eq, PartialEq)]
+pub struct ConflictDetails {
+    keys: Vec<(PartitionKey, UserKey)>,
+}
+
+impl ConflictDetails {
+    /// Returns the keys written by concurrently committed transactions that
+    /// overlap the reads of the transaction, sorted by partition and key.
+    ///
+    /// May be empty if the committed transactions could not be checked anymore,
+    /// see [`Config::max_committed_txns`](crate::Config::max_committed_txns).
+    #[must_use]
+    pub fn keys(&self) -> &[(PartitionKey, UserKey)] {
+        &self.keys
+    }
+}
+
+impl From<ConflictDetails> for Conflict {
+    fn from(_: ConflictDetails) -> Self {
+        Self
+    }
+}
+
+impl std::error::Error for ConflictDetails {}
+
+impl fmt::Display for ConflictDetails {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "transaction conflict on {} key(s)", self.keys.len())
+    }
+}
+
 /// A SSI (Serializable Snapshot Isolation) cross-partition transaction
 ///
 /// Use [`WriteTransaction::commit`] to commit changes to the partition(s).
@@ -681,12 +717,88 @@ impl WriteTransaction {
         self.cm.mark_conflict(&partition.inner.name, key);
     }
 
+    /// Creates a savepoint that the transaction can later be rolled back to,
+    /// without aborting the whole transaction.
+    ///
+    /// Savepoints can be nested: rolling back to a savepoint discards all writes
+    /// made after it, including writes covered by savepoints created later.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fjall::{Config, Keyspace, PartitionCreateOptions};
+    /// #
+    /// # let folder = tempfile::tempdir()?;
+    /// # let keyspace = Config::new(folder).open_transactional()?;
+    /// # let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+    /// let mut tx = keyspace.write_tx()?;
+    /// tx.insert(&partition, "a", "abc");
+    ///
+    /// let savepoint = tx.savepoint();
+    /// tx.insert(&partition, "b", "abc");
+    /// tx.rollback_to(&savepoint);
+    ///
+    /// assert!(tx.contains_key(&partition, "a")?);
+    /// assert!(!tx.contains_key(&partition, "b")?);
+    /// #
+    /// # Ok::<(), fjall::Error>(())
+    /// ```
+    pub fn savepoint(&self) -> Savepoint {
+        let mut savepoint = self.inner.savepoint();
+        savepoint.cm = self.cm.clone();
+        savepoint
+    }
+
+    /// Rolls back all writes made after the given savepoint.
+    ///
+    /// Reads made after the savepoint are still checked for conflicts on commit.
+    pub fn rollback_to(&mut self, savepoint: &Savepoint) {
+        self.inner.rollback_to(savepoint);
+        self.cm.rollback_writes_to(&savepoint.cm);
+    }
+
     /// Commits the transaction.
     ///
     /// # Errors
     ///
     /// Will return `Err` if an IO error occurs.
     pub fn commit(self) -> crate::Result<Result<(), Conflict>> {
+        self.commit_with_details()
+            .map(|result| result.map_err(Conflict::from))
+    }
+
+    /// Commits the transaction, reporting the keys it conflicted on if it could not be committed.
+    ///
+    /// The keys are only collected once a conflict was detected,
+    /// so this costs nothing over [`WriteTransaction::commit`] if the commit succeeds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fjall::{Config, PartitionCreateOptions};
+    /// #
+    /// # let folder = tempfile::tempdir()?;
+    /// # let keyspace = Config::new(folder).open_transactional()?;
+    /// # let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+    /// let mut tx1 = keyspace.write_tx()?;
+    /// let mut tx2 = keyspace.write_tx()?;
+    ///
+    /// tx1.insert(&partition, "a", "abc");
+    /// tx1.commit()??;
+    ///
+    /// tx2.get(&partition, "a")?;
+    /// tx2.insert(&partition, "b", "def");
+    ///
+    /// let conflict = tx2.commit_with_details()?.unwrap_err();
+    /// assert_eq!(&*conflict.keys()[0].1, b"a");
+    /// #
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if an IO error occurs.
+    pub fn commit_with_details(self) -> crate::Result<Result<(), ConflictDetails>> {
         // NOTE: We have no write set, so we are basically
         // a read-only transaction, so nothing to do here
         if self.inner.memtables.is_empty() {
@@ -700,7 +812,7 @@ impl WriteTransaction {
         })? {
             CommitOutcome::Ok => Ok(Ok(())),
             CommitOutcome::Aborted(e) => Err(e),
-            CommitOutcome::Conflicted => Ok(Err(Conflict)),
+            CommitOutcome::Conflicted(keys) => Ok(Err(ConflictDetails { keys })),
         }
     }
 
@@ -745,6 +857,127 @@ mod tests {
         Ok(TestEnv { ks, part, tmpdir })
     }
 
+    #[test]
+    fn tx_ssi_transaction_retry() -> Result<(), Box<dyn std::error::Error>> {
+        let env = setup()?;
+
+        env.part.insert("a", 0u64.to_be_bytes())?;
+
+        let mut attempts = 0;
+
+        let value = env
+            .ks
+            .transaction(3, Some(std::time::Duration::from_millis(1)), |tx| {
+                attempts += 1;
+
+                let value = tx.get(&env.part, "a")?.expect("should exist");
+                let value = u64::from_be_bytes((*value).try_into().expect("should be u64"));
+
+                // NOTE: Force a conflict on the first attempt
+                if attempts == 1 {
+                    env.part.insert("a", 10u64.to_be_bytes())?;
+                }
+
+                tx.insert(&env.part, "a", (value + 1).to_be_bytes());
+
+                Ok(value + 1)
+            })??;
+
+        assert_eq!(2, attempts);
+        assert_eq!(11, value);
+        assert_eq!(
+            Some(11u64.to_be_bytes().into()),
+            env.part.get("a")?.map(|v| v.to_vec())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn tx_ssi_transaction_retry_exhausted() -> Result<(), Box<dyn std::error::Error>> {
+        let env = setup()?;
+
+        env.part.insert("a", "a")?;
+
+        let mut attempts = 0;
+
+        let result = env.ks.transaction(3, None, |tx| {
+            attempts += 1;
+
+            tx.get(&env.part, "a")?;
+            env.part.insert("a", "b")?;
+            tx.insert(&env.part, "a", "c");
+
+            Ok(())
+        })?;
+
+        assert_eq!(Err(Conflict), result);
+        assert_eq!(3, attempts);
+        assert_eq!(Some("b".as_bytes().into()), env.part.get("a")?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn tx_ssi_savepoint_discards_conflict_keys() -> Result<(), Box<dyn std::error::Error>> {
+        let env = setup()?;
+
+        let mut tx1 = env.ks.write_tx()?;
+        let mut tx2 = env.ks.write_tx()?;
+
+        tx2.get(&env.part, "a")?;
+        tx2.insert(&env.part, "c", "c");
+
+        let savepoint = tx1.savepoint();
+        tx1.insert(&env.part, "a", "a");
+        tx1.rollback_to(&savepoint);
+        tx1.insert(&env.part, "b", "b");
+        tx1.commit()??;
+
+        // NOTE: tx1 no longer writes "a", so tx2's read is not invalidated
+        tx2.commit()??;
+
+        assert!(!env.part.contains_key("a")?);
+        assert!(env.part.contains_key("b")?);
+        assert!(env.part.contains_key("c")?);
+
+        Ok(())
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn tx_ssi_conflict_details() -> Result<(), Box<dyn std::error::Error>> {
+        let env = setup()?;
+
+        let mut tx1 = env.ks.write_tx()?;
+        let mut tx2 = env.ks.write_tx()?;
+        let mut tx3 = env.ks.write_tx()?;
+
+        tx2.get(&env.part, "a")?;
+        tx2.range(&env.part, "c".."e").count();
+        tx2.insert(&env.part, "x", "x");
+
+        tx1.insert(&env.part, "a", "a");
+        tx1.insert(&env.part, "b", "b");
+        tx1.commit()??;
+
+        tx3.insert(&env.part, "d", "d");
+        tx3.insert(&env.part, "e", "e");
+        tx3.commit()??;
+
+        let conflict = tx2.commit_with_details()?.unwrap_err();
+        assert_eq!(
+            &[
+                ("foo".into(), "a".as_bytes().into()),
+                ("foo".into(), "d".as_bytes().into()),
+            ],
+            conflict.keys(),
+        );
+        assert!(!env.part.contains_key("x")?);
+
+        Ok(())
+    }
+
     // Adapted from https://github.com/al8n/skipdb/issues/10
     #[test]
     #[allow(clippy::unwrap_used)]
@@ -2,6 +2,7 @@
 // This source code is licensed under both the Apache 2.0 and MIT License
 // (found in the LICENSE-* files in the repository)
 
+use crate::config::{Config, WriteStallMode};
 use std::sync::{atomic::AtomicU64, Arc};
 
 /// Keeps track of the size of the keyspace's write buffer
@@ -21,6 +22,18 @@ impl WriteBufferManager {
         self.load(std::sync::atomic::Ordering::Acquire)
     }
 
+    /// Rejects writes if the write buffer is saturated and the keyspace is
+    /// configured to not block writers.
+    pub fn check_saturation(&self, config: &Config) -> crate::Result<()> {
+        if config.write_stall_mode == WriteStallMode::Error
+            && self.get() >= config.max_write_buffer_size_in_bytes
+        {
+            return Err(crate::Error::WriteBufferFull);
+        }
+
+        Ok(())
+    }
+
     // Adds some bytes to the write buffer counter.
     //
     // Returns the counter *after* incrementing.
@@ -20,3 +20,23 @@ fn keyspace_recover_empty() -> fjall::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn gc_watermark_pull_up_safety_gap() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).snapshot_safety_gap(20_000).open()?;
+    let partition = keyspace.open_partition("default", Default::default())?;
+
+    for _ in 0..10_000 {
+        partition.insert("a", "a")?;
+    }
+
+    // NOTE: Wait for monitor thread tick to kick in
+    std::thread::sleep(Duration::from_secs(1));
+
+    // NOTE: The watermark is never pulled closer than the safety gap
+    assert_eq!(0, keyspace.snapshot_tracker.get_seqno_safe_to_gc());
+
+    Ok(())
+}
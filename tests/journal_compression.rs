@@ -0,0 +1,52 @@
+use fjall::{CompressionType, Config, PartitionCreateOptions};
+use test_log::test;
+
+#[test]
+#[cfg(feature = "lz4")]
+fn journal_compression_recover() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let large_value = "abcdefgh".repeat(16_000);
+
+    {
+        let keyspace = Config::new(&folder)
+            .journal_compression(CompressionType::Lz4)
+            .open()?;
+        let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+        partition.insert("a", &large_value)?;
+        partition.insert("b", "b")?;
+        partition.insert("c", "c")?;
+        partition.remove("c")?;
+
+        let mut batch = keyspace.batch();
+        batch.insert(&partition, "d", &large_value);
+        batch.remove(&partition, "b");
+        batch.commit()?;
+    }
+
+    // NOTE: The journal can be recovered even if compression is turned off now
+    {
+        let keyspace = Config::new(&folder).open()?;
+        let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+        assert_eq!(large_value.as_bytes(), &*partition.get("a")?.unwrap());
+        assert_eq!(None, partition.get("b")?);
+        assert_eq!(None, partition.get("c")?);
+        assert_eq!(large_value.as_bytes(), &*partition.get("d")?.unwrap());
+
+        partition.insert("e", &large_value)?;
+    }
+
+    {
+        let keyspace = Config::new(&folder)
+            .journal_compression(CompressionType::Lz4)
+            .open()?;
+        let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+        assert_eq!(3, partition.len()?);
+        assert_eq!(large_value.as_bytes(), &*partition.get("e")?.unwrap());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use fjall::{Config, PartitionCreateOptions};
+use test_log::test;
+
+const ITEM_COUNT: usize = 1_000;
+
+#[test]
+fn keyspace_close() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open()?;
+    let a = keyspace.open_partition("a", PartitionCreateOptions::default())?;
+    let b = keyspace.open_partition("b", PartitionCreateOptions::default())?;
+
+    for x in 0..ITEM_COUNT as u64 {
+        a.insert(x.to_be_bytes(), nanoid::nanoid!())?;
+        b.insert(x.to_be_bytes(), nanoid::nanoid!())?;
+    }
+
+    // NOTE: One sealed memtable that may not be flushed yet
+    a.rotate_memtable()?;
+    a.insert("last", "last")?;
+
+    let mut batch = keyspace.batch();
+    batch.insert(&b, "a", "a");
+
+    keyspace.close()?;
+
+    assert!(matches!(
+        a.insert("a", "a"),
+        Err(fjall::Error::KeyspaceClosed)
+    ));
+
+    assert!(matches!(batch.commit(), Err(fjall::Error::KeyspaceClosed)));
+
+    assert!(matches!(
+        a.ingest([("b", "b")]),
+        Err(fjall::Error::KeyspaceClosed)
+    ));
+
+    // NOTE: The folder is unlocked, even though partition handles are still alive
+    let keyspace = Config::new(&folder).open()?;
+    let a = keyspace.open_partition("a", PartitionCreateOptions::default())?;
+    let b = keyspace.open_partition("b", PartitionCreateOptions::default())?;
+
+    // NOTE: Nothing was replayed from the journal into memtables
+    assert_eq!(0, keyspace.write_buffer_size());
+    assert!(a.segment_count() > 0);
+    assert!(b.segment_count() > 0);
+
+    assert_eq!(ITEM_COUNT + 1, a.len()?);
+    assert_eq!(ITEM_COUNT, b.len()?);
+    assert!(a.get("last")?.is_some());
+
+    Ok(())
+}
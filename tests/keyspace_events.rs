@@ -0,0 +1,89 @@
+use fjall::{Config, Event, PartitionCreateOptions};
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use test_log::test;
+
+fn wait_for(events: &Mutex<Vec<Event>>, f: impl Fn(&Event) -> bool) -> bool {
+    let start = Instant::now();
+
+    while start.elapsed() < Duration::from_secs(10) {
+        if events.lock().expect("lock is poisoned").iter().any(&f) {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+
+    false
+}
+
+#[test]
+fn keyspace_events_flush_and_compaction() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+
+    let keyspace = Config::new(&folder)
+        .on_event({
+            let events = events.clone();
+            move |event| events.lock().expect("lock is poisoned").push(event)
+        })
+        .open()?;
+    let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+    for key in 'a'..='z' {
+        partition.insert(key.to_string(), "abc")?;
+    }
+    partition.rotate_memtable_and_wait()?;
+
+    assert!(wait_for(&events, |event| matches!(
+        event,
+        Event::CompactionFinished { .. }
+    )));
+
+    let events = events.lock().expect("lock is poisoned").clone();
+    let name: Arc<str> = "default".into();
+
+    let flush_started = events
+        .iter()
+        .position(|event| {
+            *event
+                == Event::FlushStarted {
+                    partition: name.clone(),
+                }
+        })
+        .expect("should have started flush");
+
+    let flush_finished = events
+        .iter()
+        .position(|event| matches!(event, Event::FlushFinished { .. }))
+        .expect("should have finished flush");
+
+    let compaction_started = events
+        .iter()
+        .position(|event| {
+            *event
+                == Event::CompactionStarted {
+                    partition: name.clone(),
+                }
+        })
+        .expect("should have started compaction");
+
+    assert!(flush_started < flush_finished);
+    assert!(flush_finished < compaction_started);
+
+    let Some(Event::FlushFinished {
+        partition: flushed_partition,
+        bytes,
+        ..
+    }) = events.get(flush_finished)
+    else {
+        unreachable!();
+    };
+    assert_eq!(&name, flushed_partition);
+    assert_eq!(partition.disk_space(), *bytes);
+    assert_eq!(1, partition.segment_count());
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use fjall::{Config, PartitionCreateOptions};
+use test_log::test;
+
+#[test]
+fn keyspace_list_partitions_after_reopen() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    {
+        let keyspace = Config::new(&folder).open()?;
+
+        for name in ["a", "b", "c"] {
+            let partition = keyspace.open_partition(name, PartitionCreateOptions::default())?;
+            partition.insert("key", name)?;
+        }
+    }
+
+    // NOTE: Non-partition entries should be ignored
+    let partitions_folder = folder.path().join("partitions");
+    std::fs::write(partitions_folder.join("stray.txt"), "hello")?;
+    std::fs::create_dir(partitions_folder.join(".tmp1234"))?;
+
+    for _ in 0..3 {
+        let keyspace = Config::new(&folder).open()?;
+
+        let mut names = keyspace.list_partitions();
+        names.sort();
+        assert_eq!(
+            vec!["a".into(), "b".into(), "c".into()] as Vec<std::sync::Arc<str>>,
+            names
+        );
+
+        for name in &names {
+            let partition = keyspace.open_partition(name, PartitionCreateOptions::default())?;
+            assert_eq!(Some(name.as_bytes().into()), partition.get("key")?);
+        }
+    }
+
+    Ok(())
+}
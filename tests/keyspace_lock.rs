@@ -0,0 +1,38 @@
+use fjall::Config;
+use test_log::test;
+
+#[test]
+fn keyspace_lock_second_open() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open()?;
+
+    assert!(matches!(
+        Config::new(&folder).open(),
+        Err(fjall::Error::AlreadyOpen)
+    ));
+
+    drop(keyspace);
+
+    let keyspace = Config::new(&folder).open()?;
+    let partition = keyspace.open_partition("default", Default::default())?;
+    partition.insert("a", "a")?;
+
+    Ok(())
+}
+
+#[test]
+fn keyspace_lock_stale_file() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    {
+        let _keyspace = Config::new(&folder).open()?;
+    }
+
+    // NOTE: A crashed process leaves its lock file behind, but not the lock itself
+    assert!(folder.path().join("LOCK").try_exists()?);
+
+    let _keyspace = Config::new(&folder).open()?;
+
+    Ok(())
+}
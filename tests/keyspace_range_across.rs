@@ -0,0 +1,65 @@
+use fjall::{Config, PartitionCreateOptions};
+use test_log::test;
+
+const ITEM_COUNT: u64 = 100;
+
+#[test]
+fn keyspace_range_across() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).max_open_snapshots(1).open()?;
+    let accounts = keyspace.open_partition("accounts", PartitionCreateOptions::default())?;
+    let ledger = keyspace.open_partition("ledger", PartitionCreateOptions::default())?;
+
+    let write_pair = |balance: u64| -> fjall::Result<()> {
+        let mut batch = keyspace.batch();
+        for id in 0..ITEM_COUNT {
+            let key = id.to_be_bytes();
+            batch.insert(&accounts, key, balance.to_be_bytes());
+            batch.insert(&ledger, key, balance.to_be_bytes());
+        }
+        batch.commit()
+    };
+
+    write_pair(0)?;
+
+    let iters = keyspace.range_across::<&[u8], _>(&[&accounts, &ledger], ..);
+
+    // NOTE: Both partitions share a single registered snapshot
+    assert!(accounts.try_snapshot().is_err());
+
+    let (write_result, items) = std::thread::scope(|scope| {
+        let writer = scope.spawn(|| -> fjall::Result<()> {
+            for balance in 1..=10 {
+                write_pair(balance)?;
+            }
+            Ok(())
+        });
+
+        let items = iters
+            .into_iter()
+            .map(Iterator::collect::<fjall::Result<Vec<_>>>)
+            .collect::<fjall::Result<Vec<_>>>();
+
+        (writer.join().expect("should join"), items)
+    });
+    write_result?;
+    let items = items?;
+
+    assert_eq!(2, items.len());
+
+    let (left, right) = (&items[0], &items[1]);
+    assert_eq!(ITEM_COUNT as usize, left.len());
+    assert_eq!(left, right);
+    assert!(left.iter().all(|(_, v)| &**v == 0u64.to_be_bytes()));
+
+    // NOTE: Snapshot is freed after all iterators are dropped
+    assert!(accounts.try_snapshot().is_ok());
+
+    assert_eq!(
+        Some(10u64.to_be_bytes().into()),
+        ledger.get(0u64.to_be_bytes())?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use fjall::{Config, PartitionCreateOptions};
+use test_log::test;
+
+const ITEM_COUNT: u64 = 100_000;
+
+#[test]
+fn partition_approximate_len_range() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open()?;
+    let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+    // NOTE: Spread keys over a few segments, and keep the last batch in the memtable
+    for batch in 0..4 {
+        for idx in (batch..ITEM_COUNT).step_by(4) {
+            partition.insert((idx * 10).to_be_bytes(), [])?;
+        }
+
+        if batch < 3 {
+            partition.rotate_memtable_and_wait()?;
+        }
+    }
+    assert_eq!(3, partition.segment_count());
+
+    assert_eq!(
+        ITEM_COUNT as usize,
+        partition.approximate_len_range::<&[u8], _>(..)
+    );
+
+    for (start, end) in [(0, 10_000), (25_000, 75_000), (90_000, 99_999), (500, 600)] {
+        let start_key = (start * 10u64).to_be_bytes();
+        let end_key = (end * 10u64).to_be_bytes();
+
+        let exact = partition.range(start_key..end_key).count();
+        let estimate = partition.approximate_len_range(start_key..end_key);
+
+        let tolerance = (exact / 20).max(10);
+        assert!(
+            exact.abs_diff(estimate) <= tolerance,
+            "estimate {estimate} is too far off from {exact}"
+        );
+    }
+
+    assert_eq!(
+        0,
+        partition.approximate_len_range((ITEM_COUNT * 10).to_be_bytes()..)
+    );
+
+    Ok(())
+}
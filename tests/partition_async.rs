@@ -0,0 +1,111 @@
+#![cfg(feature = "async")]
+
+use fjall::{Config, PartitionCreateOptions};
+use futures_core::Stream;
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll, Wake, Waker},
+    thread::Thread,
+};
+use test_log::test;
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Minimal executor, so the tests do not depend on an async runtime
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = std::pin::pin!(future);
+
+    let waker = Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+    let mut cx = Context::from_waker(&waker);
+
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+        std::thread::park();
+    }
+}
+
+async fn next<S: Stream + Unpin>(stream: &mut S) -> Option<S::Item> {
+    std::future::poll_fn(|cx| Pin::new(&mut *stream).poll_next(cx)).await
+}
+
+#[test]
+fn partition_async_get_insert() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open()?;
+    let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+    block_on(async {
+        partition.insert_async("a", "abc").await?;
+        assert_eq!(
+            Some("abc".as_bytes().into()),
+            partition.get_async("a").await?
+        );
+
+        partition.remove_async("a").await?;
+        assert_eq!(None, partition.get_async("a").await?);
+
+        let mut batch = keyspace.batch();
+        batch.insert(&partition, "b", "b");
+        batch.insert(&partition, "c", "c");
+        batch.commit_async().await?;
+
+        Ok::<_, fjall::Error>(())
+    })?;
+
+    assert_eq!(2, partition.len()?);
+
+    Ok(())
+}
+
+#[test]
+fn partition_async_range() -> fjall::Result<()> {
+    const ITEM_COUNT: u64 = 1_000;
+
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open()?;
+    let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+    for x in 0..ITEM_COUNT {
+        partition.insert(x.to_be_bytes(), x.to_be_bytes())?;
+    }
+
+    let keys = block_on(async {
+        let mut stream = partition.range_async(100u64.to_be_bytes()..900u64.to_be_bytes())?;
+        let mut keys = vec![];
+
+        while let Some(item) = next(&mut stream).await {
+            let (key, _) = item?;
+            keys.push(key);
+        }
+
+        Ok::<_, fjall::Error>(keys)
+    })?;
+
+    assert_eq!(800, keys.len());
+    assert_eq!(
+        &100u64.to_be_bytes(),
+        &**keys.first().expect("should exist")
+    );
+    assert_eq!(&899u64.to_be_bytes(), &**keys.last().expect("should exist"));
+
+    // NOTE: Dropping a stream early stops its thread
+    block_on(async {
+        let mut stream = partition.range_async::<&[u8], _>(..)?;
+        assert!(next(&mut stream).await.is_some());
+        Ok::<_, fjall::Error>(())
+    })?;
+
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use fjall::{BlockCache, Config, PartitionCreateOptions, PartitionHandle};
+use std::sync::Arc;
+use test_log::test;
+
+const ITEM_COUNT: u64 = 1_000;
+
+fn fill(partition: &PartitionHandle) -> fjall::Result<()> {
+    for idx in 0..ITEM_COUNT {
+        partition.insert(idx.to_be_bytes(), [0; 256])?;
+    }
+    partition.rotate_memtable_and_wait()
+}
+
+fn read_all(partition: &PartitionHandle) -> fjall::Result<()> {
+    for idx in 0..ITEM_COUNT {
+        assert!(partition.get(idx.to_be_bytes())?.is_some());
+    }
+    Ok(())
+}
+
+#[test]
+fn partition_block_cache_isolated() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let global_cache = Arc::new(BlockCache::with_capacity_bytes(16 * 1_024 * 1_024));
+    let hot_cache = Arc::new(BlockCache::with_capacity_bytes(16 * 1_024 * 1_024));
+    let cold_cache = Arc::new(BlockCache::with_capacity_bytes(32 * 1_024));
+
+    let keyspace = Config::new(&folder)
+        .block_cache(global_cache.clone())
+        .partition_block_cache("hot", hot_cache.clone())
+        .partition_block_cache("cold", cold_cache.clone())
+        .open()?;
+
+    let hot = keyspace.open_partition("hot", PartitionCreateOptions::default())?;
+    let cold = keyspace.open_partition("cold", PartitionCreateOptions::default())?;
+    fill(&hot)?;
+    fill(&cold)?;
+
+    read_all(&hot)?;
+    let hot_cached = hot_cache.len();
+    assert!(hot_cached > 0);
+
+    // NOTE: Scanning the cold partition does not evict hot blocks
+    read_all(&cold)?;
+    assert!(cold_cache.len() > 0);
+    assert!(cold_cache.size() <= cold_cache.capacity());
+    assert_eq!(hot_cached, hot_cache.len());
+
+    assert!(global_cache.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn partition_block_cache_shared() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let shared_cache = Arc::new(BlockCache::with_capacity_bytes(32 * 1_024));
+
+    let keyspace = Config::new(&folder)
+        .partition_block_cache("a", shared_cache.clone())
+        .partition_block_cache("b", shared_cache.clone())
+        .open()?;
+
+    let a = keyspace.open_partition("a", PartitionCreateOptions::default())?;
+    let b = keyspace.open_partition("b", PartitionCreateOptions::default())?;
+    fill(&a)?;
+    fill(&b)?;
+
+    read_all(&a)?;
+    let size_after_a = shared_cache.size();
+    assert!(size_after_a > 0);
+
+    // NOTE: Both partitions compete for the same capacity
+    read_all(&b)?;
+    assert!(shared_cache.size() <= shared_cache.capacity());
+    assert!(size_after_a + shared_cache.size() > shared_cache.capacity());
+
+    Ok(())
+}
+
+#[test]
+fn partition_block_cache_recover() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    {
+        let keyspace = Config::new(&folder).open()?;
+        let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+        fill(&partition)?;
+    }
+
+    let cache = Arc::new(BlockCache::with_capacity_bytes(16 * 1_024 * 1_024));
+
+    let keyspace = Config::new(&folder)
+        .partition_block_cache("default", cache.clone())
+        .open()?;
+    let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+    read_all(&partition)?;
+    assert!(!cache.is_empty());
+
+    Ok(())
+}
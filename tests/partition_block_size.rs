@@ -0,0 +1,53 @@
+use fjall::{Config, PartitionCreateOptions};
+use test_log::test;
+
+const ITEM_COUNT: u64 = 10_000;
+
+#[test]
+fn partition_block_size_mixed() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    {
+        let keyspace = Config::new(&folder).open()?;
+
+        let small = keyspace.open_partition(
+            "small",
+            PartitionCreateOptions::default().block_size(4 * 1_024),
+        )?;
+        let large = keyspace.open_partition(
+            "large",
+            PartitionCreateOptions::default().block_size(64 * 1_024),
+        )?;
+
+        for partition in [&small, &large] {
+            for idx in 0..ITEM_COUNT {
+                partition.insert(idx.to_be_bytes(), idx.to_string())?;
+            }
+            partition.rotate_memtable_and_wait()?;
+            assert_eq!(1, partition.segment_count());
+        }
+    }
+
+    {
+        let keyspace = Config::new(&folder).open()?;
+
+        let small = keyspace.open_partition("small", PartitionCreateOptions::default())?;
+        let large = keyspace.open_partition("large", PartitionCreateOptions::default())?;
+
+        assert_eq!(4 * 1_024, small.config.data_block_size);
+        assert_eq!(64 * 1_024, large.config.data_block_size);
+
+        for partition in [&small, &large] {
+            assert_eq!(ITEM_COUNT as usize, partition.len()?);
+
+            for idx in (0..ITEM_COUNT).step_by(97) {
+                assert_eq!(
+                    Some(idx.to_string().as_bytes().into()),
+                    partition.get(idx.to_be_bytes())?
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
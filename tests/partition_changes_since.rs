@@ -0,0 +1,133 @@
+use fjall::{Change, Config, KvSeparationOptions, PartitionCreateOptions, ValueType};
+use test_log::test;
+
+fn check(keyspace: &fjall::Keyspace, partition: &fjall::PartitionHandle) -> fjall::Result<()> {
+    let value = "a".repeat(1_000);
+
+    for x in 0..10u64 {
+        partition.insert(x.to_be_bytes(), &value)?;
+    }
+    partition.rotate_memtable_and_wait()?;
+
+    let changes = partition.changes_since(None)?;
+    assert_eq!(10, changes.len());
+    assert_eq!(0, changes[0].seqno);
+
+    let checkpoint = keyspace.instant() - 1;
+    assert_eq!(Some(checkpoint), changes.last().map(|change| change.seqno));
+
+    let mut expected = vec![];
+
+    let seqno = keyspace.instant();
+    partition.remove(3u64.to_be_bytes())?;
+    expected.push(Change {
+        seqno,
+        key: 3u64.to_be_bytes().into(),
+        value_type: ValueType::Tombstone,
+        value: [].into(),
+    });
+    partition.rotate_memtable_and_wait()?;
+
+    let seqno = keyspace.instant();
+    let mut batch = keyspace.batch();
+    batch.insert(partition, "b", &value);
+    batch.insert(partition, "a", "new");
+    batch.commit()?;
+    expected.push(Change {
+        seqno,
+        key: "a".as_bytes().into(),
+        value_type: ValueType::Value,
+        value: "new".as_bytes().into(),
+    });
+    expected.push(Change {
+        seqno,
+        key: "b".as_bytes().into(),
+        value_type: ValueType::Value,
+        value: value.as_bytes().into(),
+    });
+
+    assert_eq!(expected, partition.changes_since(Some(checkpoint))?);
+
+    // NOTE: Resume from the last seen change
+    let checkpoint = seqno;
+
+    let seqno = keyspace.instant();
+    partition.insert("c", "abc")?;
+
+    assert_eq!(
+        vec![Change {
+            seqno,
+            key: "c".as_bytes().into(),
+            value_type: ValueType::Value,
+            value: "abc".as_bytes().into(),
+        }],
+        partition.changes_since(Some(checkpoint))?,
+    );
+    assert!(partition.changes_since(Some(seqno))?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn partition_changes_since() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open()?;
+    let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+    check(&keyspace, &partition)
+}
+
+#[test]
+fn partition_changes_since_blob() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open()?;
+    let partition = keyspace.open_partition(
+        "default",
+        PartitionCreateOptions::default()
+            .with_kv_separation(KvSeparationOptions::default().separation_threshold(100)),
+    )?;
+
+    check(&keyspace, &partition)
+}
+
+#[test]
+fn partition_changes_since_concurrent_rotation() -> fjall::Result<()> {
+    const ITEM_COUNT: u64 = 2_000;
+
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open()?;
+    let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+    let done = std::sync::atomic::AtomicBool::new(false);
+
+    let seen_counts = std::thread::scope(|s| {
+        let rotator = s.spawn(|| {
+            while !done.load(std::sync::atomic::Ordering::Relaxed) {
+                partition.rotate_memtable()?;
+            }
+            Ok::<_, fjall::Error>(())
+        });
+
+        let seen_counts = (0..ITEM_COUNT)
+            .map(|x| {
+                partition.insert(x.to_be_bytes(), "abc")?;
+                Ok(partition.changes_since(None)?.len())
+            })
+            .collect::<fjall::Result<Vec<_>>>();
+
+        done.store(true, std::sync::atomic::Ordering::Relaxed);
+        rotator.join().expect("should join")?;
+
+        seen_counts
+    })?;
+
+    // NOTE: Every change has to be seen, even while its memtable is being rotated
+    for (idx, seen_count) in seen_counts.into_iter().enumerate() {
+        assert_eq!(idx + 1, seen_count);
+    }
+
+    Ok(())
+}
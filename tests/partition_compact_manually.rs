@@ -0,0 +1,38 @@
+use fjall::{Config, PartitionCreateOptions};
+use test_log::test;
+
+const ITEM_COUNT: usize = 1_000;
+
+#[test]
+fn partition_compact_manually() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).compaction_workers(0).open()?;
+    let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+    let report = partition.compact_manually()?;
+    assert_eq!(0, report.segments_merged);
+    assert_eq!(0, report.bytes_reclaimed);
+
+    for _ in 0..4 {
+        for x in 0..ITEM_COUNT as u64 {
+            partition.insert(x.to_be_bytes(), nanoid::nanoid!())?;
+        }
+        partition.rotate_memtable_and_wait()?;
+    }
+
+    assert_eq!(4, partition.segment_count());
+
+    let report = partition.compact_manually()?;
+    assert_eq!(4, report.segments_merged);
+    assert!(report.bytes_reclaimed > 0);
+
+    assert_eq!(1, partition.segment_count());
+    assert_eq!(ITEM_COUNT, partition.len()?);
+
+    // NOTE: Nothing left to do
+    let report = partition.compact_manually()?;
+    assert_eq!(0, report.segments_merged);
+
+    Ok(())
+}
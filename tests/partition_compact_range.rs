@@ -0,0 +1,100 @@
+use fjall::{CompressionType, Config, PartitionCreateOptions};
+use test_log::test;
+
+const ITEM_COUNT: usize = 1_000;
+
+fn value() -> String {
+    nanoid::nanoid!(1_000)
+}
+
+#[test]
+fn partition_compact_range() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder)
+        .compaction_workers(0)
+        .snapshot_safety_gap(1)
+        .open()?;
+    let partition = keyspace.open_partition(
+        "default",
+        PartitionCreateOptions::default().compression(CompressionType::None),
+    )?;
+
+    for region in ["a", "b"] {
+        for x in 0..ITEM_COUNT {
+            partition.insert(format!("{region}{x:04}"), value())?;
+        }
+        partition.rotate_memtable_and_wait()?;
+    }
+
+    let untouched_segment = partition
+        .segment_summary()
+        .into_iter()
+        .find(|segment| &*segment.key_range.0 == b"b0000")
+        .expect("should exist");
+
+    for x in 0..ITEM_COUNT {
+        partition.remove(format!("a{x:04}"))?;
+    }
+    partition.rotate_memtable_and_wait()?;
+
+    assert_eq!(3, partition.segment_count());
+
+    // NOTE: Nothing to do outside the partition's data
+    let report = partition.compact_range("x".."z")?;
+    assert_eq!(0, report.segments_merged);
+
+    // NOTE: Move the GC watermark past the tombstones
+    partition.insert("c", "c")?;
+    assert!(keyspace.gc_now() > 0);
+
+    let disk_space_before = partition.disk_space();
+
+    let report = partition.compact_range("a".."b")?;
+    assert_eq!(2, report.segments_merged);
+    assert!(report.bytes_reclaimed > (ITEM_COUNT * 1_000) as u64);
+    assert!(partition.disk_space() < disk_space_before / 2);
+
+    // NOTE: Region "b" stays in its segment
+    let segments = partition.segment_summary();
+    assert_eq!(vec![untouched_segment], segments);
+
+    assert_eq!(ITEM_COUNT + 1, partition.len()?);
+    assert!(partition.get("a0000")?.is_none());
+    assert!(partition.get("b0000")?.is_some());
+
+    Ok(())
+}
+
+#[test]
+fn partition_compact_range_snapshot() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    // NOTE: Keep the monitor from pulling the GC watermark past the
+    // inserted items before the snapshot is opened
+    let keyspace = Config::new(&folder)
+        .compaction_workers(0)
+        .snapshot_safety_gap(ITEM_COUNT as u64 * 2)
+        .open()?;
+    let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+    for x in 0..ITEM_COUNT {
+        partition.insert(format!("a{x:04}"), value())?;
+    }
+    partition.rotate_memtable_and_wait()?;
+
+    let snapshot = partition.snapshot();
+
+    for x in 0..ITEM_COUNT {
+        partition.remove(format!("a{x:04}"))?;
+    }
+    partition.rotate_memtable_and_wait()?;
+
+    let report = partition.compact_range("a".."b")?;
+    assert_eq!(2, report.segments_merged);
+
+    assert_eq!(ITEM_COUNT, snapshot.len()?);
+    assert_eq!(0, partition.len()?);
+
+    Ok(())
+}
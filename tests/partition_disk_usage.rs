@@ -0,0 +1,79 @@
+use fjall::{CompressionType, Config, KvSeparationOptions, PartitionCreateOptions};
+use rand::RngCore;
+use test_log::test;
+
+const ITEM_COUNT: u64 = 1_000;
+const VALUE_SIZE: usize = 1_000;
+
+fn random_value() -> Vec<u8> {
+    let mut value = vec![0; VALUE_SIZE];
+    rand::thread_rng().fill_bytes(&mut value);
+    value
+}
+
+#[test]
+fn partition_disk_usage() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open()?;
+    let partition = keyspace.open_partition(
+        "default",
+        PartitionCreateOptions::default().compression(CompressionType::None),
+    )?;
+
+    assert_eq!(0, partition.disk_usage().total());
+
+    for x in 0..ITEM_COUNT {
+        partition.insert(x.to_be_bytes(), random_value())?;
+    }
+    assert_eq!(0, partition.disk_usage().total());
+
+    partition.rotate_memtable_and_wait()?;
+
+    let usage = partition.disk_usage();
+    let payload = ITEM_COUNT * VALUE_SIZE as u64;
+
+    assert_eq!(usage.levels[0], usage.segments());
+    assert_eq!(0, usage.blobs);
+    #[cfg(feature = "bloom")]
+    assert!(usage.bloom_filters > 0);
+    #[cfg(not(feature = "bloom"))]
+    assert_eq!(0, usage.bloom_filters);
+    assert!(usage.total() > payload);
+    assert!(usage.total() < payload * 12 / 10);
+    assert_eq!(partition.disk_space(), usage.total());
+
+    // NOTE: Journals are shared, so they are accounted for by the keyspace
+    assert!(keyspace.journal_disk_space() > 0);
+
+    Ok(())
+}
+
+#[test]
+fn partition_disk_usage_blob() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open()?;
+    let partition = keyspace.open_partition(
+        "default",
+        PartitionCreateOptions::default()
+            .with_kv_separation(KvSeparationOptions::default().separation_threshold(100)),
+    )?;
+
+    for x in 0..ITEM_COUNT {
+        partition.insert(x.to_be_bytes(), random_value())?;
+    }
+    partition.rotate_memtable_and_wait()?;
+
+    let usage = partition.disk_usage();
+    let payload = ITEM_COUNT * VALUE_SIZE as u64;
+
+    // NOTE: Values are stored in the value log, the index tree only stores handles
+    assert!(usage.blobs >= payload);
+    assert!(usage.blobs < payload * 12 / 10);
+    assert!(usage.segments() > 0);
+    assert!(usage.segments() < payload / 10);
+    assert_eq!(partition.disk_space(), usage.total());
+
+    Ok(())
+}
@@ -0,0 +1,205 @@
+use fjall::{Config, KvSeparationOptions, PartitionCreateOptions};
+use test_log::test;
+
+#[test]
+fn partition_insert_and_fetch() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open()?;
+    let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+    assert_eq!(None, partition.insert_and_fetch("a", "abc")?);
+    assert_eq!(
+        Some("abc".as_bytes().into()),
+        partition.insert_and_fetch("a", "def")?
+    );
+
+    // NOTE: Previous value may come from a disk segment
+    partition.rotate_memtable_and_wait()?;
+    assert_eq!(
+        Some("def".as_bytes().into()),
+        partition.insert_and_fetch("a", "ghi")?
+    );
+
+    assert_eq!(Some("ghi".as_bytes().into()), partition.get("a")?);
+
+    Ok(())
+}
+
+#[test]
+fn partition_remove_and_fetch() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open()?;
+    let partition = keyspace.open_partition(
+        "default",
+        PartitionCreateOptions::default().with_kv_separation(KvSeparationOptions::default()),
+    )?;
+
+    let large_value = "a".repeat(10_000);
+
+    assert_eq!(None, partition.remove_and_fetch("a")?);
+
+    partition.insert("a", &large_value)?;
+    partition.rotate_memtable_and_wait()?;
+
+    assert_eq!(
+        Some(large_value.as_bytes().into()),
+        partition.remove_and_fetch("a")?
+    );
+    assert_eq!(None, partition.get("a")?);
+    assert_eq!(None, partition.remove_and_fetch("a")?);
+
+    Ok(())
+}
+
+#[test]
+fn partition_insert_and_fetch_concurrent() -> fjall::Result<()> {
+    const THREAD_COUNT: usize = 4;
+    const WRITE_COUNT: usize = 250;
+
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open()?;
+    let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+    partition.insert("counter", 0u64.to_be_bytes())?;
+
+    // NOTE: Every fetching write observes exactly one predecessor,
+    // so all previous values are unique
+    let mut seen = std::thread::scope(|s| {
+        let handles = (0..THREAD_COUNT)
+            .map(|idx| {
+                let partition = &partition;
+
+                s.spawn(move || {
+                    let mut seen = vec![];
+
+                    for x in 0..WRITE_COUNT {
+                        let value = ((idx * WRITE_COUNT + x + 1) as u64).to_be_bytes();
+                        let prev = partition
+                            .insert_and_fetch("counter", value)?
+                            .expect("should exist");
+                        seen.push(prev);
+                    }
+
+                    Ok::<_, fjall::Error>(seen)
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut seen = vec![];
+        for handle in handles {
+            seen.extend(handle.join().expect("should join")?);
+        }
+        Ok::<_, fjall::Error>(seen)
+    })?;
+
+    seen.push(partition.get("counter")?.expect("should exist"));
+    seen.sort();
+    seen.dedup();
+    assert_eq!(THREAD_COUNT * WRITE_COUNT + 1, seen.len());
+
+    Ok(())
+}
+
+#[test]
+fn partition_compare_and_swap() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open()?;
+    let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+    // Success
+    partition.insert("a", "abc")?;
+    assert!(partition.compare_and_swap("a", Some(b"abc"), Some(b"def"))?);
+    assert_eq!(Some("def".as_bytes().into()), partition.get("a")?);
+
+    // Mismatch
+    assert!(!partition.compare_and_swap("a", Some(b"abc"), Some(b"ghi"))?);
+    assert!(!partition.compare_and_swap("a", None, Some(b"ghi"))?);
+    assert_eq!(Some("def".as_bytes().into()), partition.get("a")?);
+
+    // Swap to deletion
+    partition.rotate_memtable_and_wait()?;
+    assert!(partition.compare_and_swap("a", Some(b"def"), None)?);
+    assert_eq!(None, partition.get("a")?);
+
+    Ok(())
+}
+
+#[test]
+fn partition_compare_and_swap_absent_key() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open()?;
+    let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+    assert!(!partition.compare_and_swap("a", Some(b"abc"), Some(b"def"))?);
+    assert_eq!(None, partition.get("a")?);
+
+    assert!(!partition.compare_and_swap("a", Some(b"abc"), None)?);
+    assert_eq!(0, keyspace.instant());
+
+    assert!(partition.compare_and_swap("a", None, Some(b"abc"))?);
+    assert_eq!(Some("abc".as_bytes().into()), partition.get("a")?);
+
+    // NOTE: Deleted keys count as absent
+    partition.remove("a")?;
+    assert!(partition.compare_and_swap("a", None, Some(b"def"))?);
+    assert_eq!(Some("def".as_bytes().into()), partition.get("a")?);
+
+    Ok(())
+}
+
+#[test]
+fn partition_compare_and_swap_concurrent() -> fjall::Result<()> {
+    const THREAD_COUNT: u64 = 4;
+    const INCREMENT_COUNT: u64 = 100;
+
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open()?;
+    let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+    partition.insert("counter", 0u64.to_be_bytes())?;
+
+    std::thread::scope(|s| {
+        let handles = (0..THREAD_COUNT)
+            .map(|_| {
+                let partition = &partition;
+
+                s.spawn(move || {
+                    let mut increments = 0;
+
+                    while increments < INCREMENT_COUNT {
+                        let current = partition.get("counter")?.expect("should exist");
+                        let next =
+                            (u64::from_be_bytes((*current).try_into().expect("should be u64")) + 1)
+                                .to_be_bytes();
+
+                        if partition.compare_and_swap("counter", Some(&current), Some(&next))? {
+                            increments += 1;
+                        }
+                    }
+
+                    Ok::<_, fjall::Error>(())
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for handle in handles {
+            handle.join().expect("should join")?;
+        }
+
+        Ok::<_, fjall::Error>(())
+    })?;
+
+    let counter = partition.get("counter")?.expect("should exist");
+    assert_eq!(
+        THREAD_COUNT * INCREMENT_COUNT,
+        u64::from_be_bytes((*counter).try_into().expect("should be u64"))
+    );
+
+    Ok(())
+}
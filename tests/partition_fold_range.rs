@@ -0,0 +1,32 @@
+use fjall::{Config, PartitionCreateOptions};
+use test_log::test;
+
+#[test]
+fn partition_fold_range_sum() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open()?;
+    let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+    for x in 0_u64..100 {
+        partition.insert(x.to_be_bytes(), (x * 3).to_be_bytes())?;
+    }
+
+    let sum = partition.fold_range(
+        10_u64.to_be_bytes()..50_u64.to_be_bytes(),
+        0,
+        |acc, _, v| {
+            let mut buf = [0; 8];
+            buf.copy_from_slice(v);
+            acc + u64::from_be_bytes(buf)
+        },
+    )?;
+
+    let expected = (10_u64..50).map(|x| x * 3).sum::<u64>();
+    assert_eq!(expected, sum);
+
+    let count = partition.fold_range::<&[u8], _, _>(.., 0, |acc, _, _| acc + 1)?;
+    assert_eq!(100, count);
+
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use fjall::{Config, KeyVersion, KvSeparationOptions, PartitionCreateOptions};
+use test_log::test;
+
+fn check(keyspace: &fjall::Keyspace, partition: &fjall::PartitionHandle) -> fjall::Result<()> {
+    // NOTE: Keep all versions from being dropped by compaction
+    let _snapshot = partition.snapshot();
+
+    let mut expected = vec![];
+
+    let mut write = |value: Option<String>| -> fjall::Result<()> {
+        let seqno = keyspace.instant();
+        match &value {
+            Some(value) => partition.insert("key", value)?,
+            None => partition.remove("key")?,
+        }
+        partition.insert("other", "abc")?;
+
+        expected.insert(
+            0,
+            KeyVersion {
+                seqno,
+                value: value.map(|v| v.as_bytes().into()),
+            },
+        );
+        Ok(())
+    };
+
+    write(Some("a".repeat(1_000)))?;
+    write(Some("b".repeat(1_000)))?;
+    partition.rotate_memtable_and_wait()?;
+
+    write(None)?;
+    write(Some("c".repeat(1_000)))?;
+    partition.rotate_memtable_and_wait()?;
+
+    write(Some("d".repeat(1_000)))?;
+    write(None)?;
+
+    assert_eq!(expected, partition.get_all_versions("key")?);
+    assert_eq!(6, expected.len());
+    assert!(partition.get_all_versions("nope")?.is_empty());
+
+    // NOTE: Versions survive compaction while a snapshot holds them
+    partition.compact_manually()?;
+    assert_eq!(expected, partition.get_all_versions("key")?);
+
+    Ok(())
+}
+
+#[test]
+fn partition_get_all_versions() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open()?;
+    let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+    check(&keyspace, &partition)
+}
+
+#[test]
+fn partition_get_all_versions_blob() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open()?;
+    let partition = keyspace.open_partition(
+        "default",
+        PartitionCreateOptions::default()
+            .with_kv_separation(KvSeparationOptions::default().separation_threshold(100)),
+    )?;
+
+    check(&keyspace, &partition)
+}
+
+#[test]
+fn partition_get_all_versions_concurrent_rotation() -> fjall::Result<()> {
+    const VERSION_COUNT: usize = 2_000;
+
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open()?;
+    let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+    partition.insert("seed", "abc")?;
+
+    // NOTE: Keep all versions from being dropped by compaction
+    let _snapshot = partition.snapshot();
+
+    let done = std::sync::atomic::AtomicBool::new(false);
+
+    let seen_counts = std::thread::scope(|s| {
+        let rotator = s.spawn(|| {
+            while !done.load(std::sync::atomic::Ordering::Relaxed) {
+                partition.rotate_memtable()?;
+            }
+            Ok::<_, fjall::Error>(())
+        });
+
+        let seen_counts = (0..VERSION_COUNT)
+            .map(|x| {
+                partition.insert("key", x.to_string())?;
+                Ok(partition.get_all_versions("key")?.len())
+            })
+            .collect::<fjall::Result<Vec<_>>>();
+
+        done.store(true, std::sync::atomic::Ordering::Relaxed);
+        rotator.join().expect("should join")?;
+
+        seen_counts
+    })?;
+
+    // NOTE: Every version has to be seen, even while its memtable is being rotated
+    for (idx, seen_count) in seen_counts.into_iter().enumerate() {
+        assert_eq!(idx + 1, seen_count);
+    }
+
+    Ok(())
+}
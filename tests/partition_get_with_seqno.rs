@@ -0,0 +1,82 @@
+use fjall::{Config, KvSeparationOptions, PartitionCreateOptions};
+use test_log::test;
+
+fn check(keyspace: &fjall::Keyspace, partition: &fjall::PartitionHandle) -> fjall::Result<()> {
+    let value = "a".repeat(1_000);
+
+    let a = keyspace.instant();
+    partition.insert("a", &value)?;
+
+    let b = keyspace.instant();
+    partition.insert("b", &value)?;
+
+    assert_eq!(
+        Some((value.as_bytes().into(), a)),
+        partition.get_with_seqno("a")?,
+    );
+
+    // NOTE: Seqnos survive flushing
+    partition.rotate_memtable_and_wait()?;
+
+    let b_new = keyspace.instant();
+    partition.insert("b", "new")?;
+    partition.insert("c", &value)?;
+    partition.remove("c")?;
+
+    assert_eq!(
+        Some((value.as_bytes().into(), a)),
+        partition.get_with_seqno("a")?,
+    );
+    assert_eq!(
+        Some(("new".as_bytes().into(), b_new)),
+        partition.get_with_seqno("b")?,
+    );
+    assert!(b < b_new);
+    assert_eq!(None, partition.get_with_seqno("c")?);
+    assert_eq!(None, partition.get_with_seqno("d")?);
+
+    let items = partition
+        .range_with_seqno::<&str, _>(..)
+        .collect::<fjall::Result<Vec<_>>>()?;
+
+    assert_eq!(
+        vec![
+            (b"a".into(), value.as_bytes().into(), a),
+            (b"b".into(), b"new".into(), b_new),
+        ],
+        items,
+    );
+
+    let items = partition
+        .range_with_seqno("b"..)
+        .rev()
+        .collect::<fjall::Result<Vec<_>>>()?;
+
+    assert_eq!(vec![(b"b".into(), b"new".into(), b_new)], items);
+
+    Ok(())
+}
+
+#[test]
+fn partition_get_with_seqno() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open()?;
+    let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+    check(&keyspace, &partition)
+}
+
+#[test]
+fn partition_get_with_seqno_blob() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open()?;
+    let partition = keyspace.open_partition(
+        "default",
+        PartitionCreateOptions::default()
+            .with_kv_separation(KvSeparationOptions::default().separation_threshold(100)),
+    )?;
+
+    check(&keyspace, &partition)
+}
@@ -0,0 +1,172 @@
+use fjall::{Config, PartitionCreateOptions, Slice};
+use test_log::test;
+
+#[test]
+fn partition_indexed_lookup() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open()?;
+
+    // NOTE: Values are "<city>:<name>", indexed by city
+    let users = keyspace.open_indexed_partition(
+        "users",
+        PartitionCreateOptions::default(),
+        |_, value| {
+            value
+                .split(|&b| b == b':')
+                .next()
+                .map(<[u8]>::to_vec)
+                .into_iter()
+                .collect()
+        },
+    )?;
+
+    users.insert("1", "berlin:alice")?;
+    users.insert("2", "berlin:bob")?;
+    users.insert("3", "paris:carol")?;
+    users.insert("4", "ber:dave")?;
+
+    assert_eq!(
+        vec![Slice::from(*b"1"), Slice::from(*b"2")],
+        users.lookup_by_index("berlin")?,
+    );
+    assert_eq!(vec![Slice::from(*b"3")], users.lookup_by_index("paris")?);
+    assert_eq!(vec![Slice::from(*b"4")], users.lookup_by_index("ber")?);
+    assert!(users.lookup_by_index("rome")?.is_empty());
+
+    // Update moves the index entry
+    users.insert("2", "paris:bob")?;
+    assert_eq!(vec![Slice::from(*b"1")], users.lookup_by_index("berlin")?);
+    assert_eq!(
+        vec![Slice::from(*b"2"), Slice::from(*b"3")],
+        users.lookup_by_index("paris")?,
+    );
+
+    // Delete removes the index entry
+    users.remove("3")?;
+    assert_eq!(vec![Slice::from(*b"2")], users.lookup_by_index("paris")?);
+    assert_eq!(None, users.get("3")?);
+
+    // Removing a non-existing key is a no-op
+    users.remove("5")?;
+
+    assert_eq!(3, users.snapshot().len()?);
+    assert_eq!(3, users.index_snapshot().len()?);
+
+    Ok(())
+}
+
+#[test]
+fn partition_indexed_recover() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    {
+        let keyspace = Config::new(&folder).open()?;
+        let users = keyspace.open_indexed_partition(
+            "users",
+            PartitionCreateOptions::default(),
+            |_, value| vec![value.to_vec()],
+        )?;
+
+        users.insert("1", "berlin")?;
+        users.insert("2", "paris")?;
+    }
+
+    {
+        let keyspace = Config::new(&folder).open()?;
+        assert!(keyspace.partition_exists("users#idx"));
+
+        let users = keyspace.open_indexed_partition(
+            "users",
+            PartitionCreateOptions::default(),
+            |_, value| vec![value.to_vec()],
+        )?;
+
+        assert_eq!(vec![Slice::from(*b"2")], users.lookup_by_index("paris")?);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn partition_indexed_shared_lock() -> fjall::Result<()> {
+    const THREADS: u64 = 4;
+    const WRITES_PER_THREAD: u64 = 250;
+
+    let folder = tempfile::tempdir()?;
+    let keyspace = Config::new(&folder).open()?;
+
+    // NOTE: Every thread opens its own handle to the same partition
+    let handles = (0..THREADS)
+        .map(|thread| {
+            let users = keyspace.open_indexed_partition(
+                "users",
+                PartitionCreateOptions::default(),
+                |_, value| vec![value.to_vec()],
+            )?;
+
+            Ok(std::thread::spawn(move || -> fjall::Result<()> {
+                for x in 0..WRITES_PER_THREAD {
+                    users.insert("alice", format!("{thread}-{x}"))?;
+                }
+                Ok(())
+            }))
+        })
+        .collect::<fjall::Result<Vec<_>>>()?;
+
+    for handle in handles {
+        handle.join().expect("should join")?;
+    }
+
+    let users = keyspace.open_indexed_partition(
+        "users",
+        PartitionCreateOptions::default(),
+        |_, value| vec![value.to_vec()],
+    )?;
+
+    // NOTE: Only the index entry of the final value is left
+    let value = users.get("alice")?.expect("should exist");
+    assert_eq!(vec![Slice::from(*b"alice")], users.lookup_by_index(&value)?);
+    assert_eq!(1, users.index_snapshot().len()?);
+
+    Ok(())
+}
+
+#[test]
+fn partition_indexed_reject_plain_open() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    {
+        let keyspace = Config::new(&folder).open()?;
+        let users = keyspace.open_indexed_partition(
+            "users",
+            PartitionCreateOptions::default(),
+            |_, value| vec![value.to_vec()],
+        )?;
+        users.insert("1", "berlin")?;
+
+        assert!(matches!(
+            keyspace.open_partition("users", PartitionCreateOptions::default()),
+            Err(fjall::Error::PartitionIndexed),
+        ));
+    }
+
+    {
+        let keyspace = Config::new(&folder).open()?;
+
+        // NOTE: The index is recovered, so the restriction still applies
+        assert!(matches!(
+            keyspace.open_partition("users", PartitionCreateOptions::default()),
+            Err(fjall::Error::PartitionIndexed),
+        ));
+
+        let users = keyspace.open_indexed_partition(
+            "users",
+            PartitionCreateOptions::default(),
+            |_, value| vec![value.to_vec()],
+        )?;
+        assert_eq!(vec![Slice::from(*b"1")], users.lookup_by_index("berlin")?);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use fjall::{Config, PartitionCreateOptions};
+use test_log::test;
+
+const ITEM_COUNT: u64 = 100_000;
+
+#[test]
+fn partition_ingest() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    {
+        let keyspace = Config::new(&folder).open()?;
+        let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+        // NOTE: Older unflushed writes are overwritten by the ingested items
+        partition.insert(0u64.to_be_bytes(), "old")?;
+        partition.insert(ITEM_COUNT.to_be_bytes(), "after")?;
+
+        partition.ingest((0..ITEM_COUNT).map(|x| (x.to_be_bytes(), x.to_le_bytes())))?;
+        assert!(partition.segment_count() > 1);
+
+        partition.insert(1u64.to_be_bytes(), "new")?;
+
+        assert_eq!(ITEM_COUNT + 1, partition.len()? as u64);
+        assert_eq!(
+            Some(0u64.to_le_bytes().into()),
+            partition.get(0u64.to_be_bytes())?
+        );
+        assert_eq!(
+            Some("new".as_bytes().into()),
+            partition.get(1u64.to_be_bytes())?
+        );
+    }
+
+    {
+        let keyspace = Config::new(&folder).open()?;
+        let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+        assert_eq!(ITEM_COUNT + 1, partition.len()? as u64);
+
+        for (idx, kv) in partition.iter().take(ITEM_COUNT as usize).enumerate() {
+            let (key, value) = kv?;
+            let idx = idx as u64;
+
+            assert_eq!(&idx.to_be_bytes(), &*key);
+
+            if idx == 1 {
+                assert_eq!(b"new", &*value);
+            } else {
+                assert_eq!(&idx.to_le_bytes(), &*value);
+            }
+        }
+
+        assert_eq!(
+            Some("after".as_bytes().into()),
+            partition.get(ITEM_COUNT.to_be_bytes())?
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn partition_ingest_unsorted() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open()?;
+    let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+    let result = partition.ingest([("a", "a"), ("c", "c"), ("b", "b")]);
+    assert!(matches!(result, Err(fjall::Error::UnsortedIngestion)));
+
+    let result = partition.ingest([("a", "a"), ("a", "b")]);
+    assert!(matches!(result, Err(fjall::Error::UnsortedIngestion)));
+
+    assert!(partition.is_empty()?);
+    assert_eq!(0, partition.segment_count());
+
+    Ok(())
+}
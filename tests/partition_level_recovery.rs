@@ -0,0 +1,51 @@
+use fjall::{compaction::Strategy, Config, PartitionCreateOptions};
+use test_log::test;
+
+const ITEM_COUNT: u64 = 100;
+
+#[test]
+fn partition_level_membership_recovery() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let create_options = || {
+        PartitionCreateOptions::default().compaction_strategy(Strategy::Leveled(
+            fjall::compaction::Leveled {
+                l0_threshold: 2,
+                ..Default::default()
+            },
+        ))
+    };
+
+    let summary = {
+        let keyspace = Config::new(&folder).compaction_workers(0).open()?;
+        let partition = keyspace.open_partition("default", create_options())?;
+
+        for batch in 0..3 {
+            for x in 0..ITEM_COUNT {
+                // NOTE: First two batches overlap, so they are actually merged
+                let key = if batch == 2 { ITEM_COUNT + x } else { x };
+                partition.insert(key.to_be_bytes(), batch.to_string())?;
+            }
+            partition.rotate_memtable_and_wait()?;
+
+            if batch == 1 {
+                let report = partition.compact_manually()?;
+                assert_eq!(2, report.segments_merged);
+            }
+        }
+
+        let summary = partition.segment_summary();
+        assert_eq!(2, summary.len());
+        summary
+    };
+
+    for _ in 0..3 {
+        let keyspace = Config::new(&folder).compaction_workers(0).open()?;
+        let partition = keyspace.open_partition("default", create_options())?;
+
+        assert_eq!(summary, partition.segment_summary());
+        assert_eq!(2 * ITEM_COUNT as usize, partition.len()?);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use fjall::{AnyTree, Config, PartitionCreateOptions};
+use test_log::test;
+
+#[test]
+fn partition_level_summary() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open()?;
+    let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+    assert!(partition
+        .level_summary()
+        .iter()
+        .all(|level| level.segment_count == 0 && level.size == 0 && level.key_range.is_none()));
+
+    for key in ["a", "b", "c"] {
+        partition.insert(key, "old")?;
+    }
+    partition.rotate_memtable_and_wait()?;
+
+    for key in ["b", "c", "d"] {
+        partition.insert(key, "new")?;
+    }
+    partition.rotate_memtable_and_wait()?;
+
+    let levels = partition.level_summary();
+    let first = levels.first().expect("should exist");
+    assert_eq!(2, first.segment_count);
+    assert_eq!(2, first.overlap_depth);
+    assert_eq!(Some((b"a".into(), b"d".into())), first.key_range);
+    assert_eq!(partition.disk_space(), first.size);
+
+    let AnyTree::Standard(tree) = &partition.tree else {
+        panic!("should be standard tree");
+    };
+    tree.major_compact(u64::MAX, 0)?;
+
+    for key in ["x", "y", "z"] {
+        partition.insert(key, "abc")?;
+    }
+    partition.rotate_memtable_and_wait()?;
+
+    let levels = partition.level_summary();
+    assert_eq!(7, levels.len());
+
+    let first = levels.first().expect("should exist");
+    assert_eq!(1, first.segment_count);
+    assert_eq!(1, first.overlap_depth);
+    assert_eq!(Some((b"x".into(), b"z".into())), first.key_range);
+
+    let last = levels.last().expect("should exist");
+    assert_eq!(6, last.level);
+    assert_eq!(1, last.segment_count);
+    assert_eq!(1, last.overlap_depth);
+    assert_eq!(Some((b"a".into(), b"d".into())), last.key_range);
+
+    for level in levels.iter().skip(1).take(5) {
+        assert_eq!(0, level.segment_count);
+        assert_eq!(0, level.size);
+        assert_eq!(0, level.overlap_depth);
+    }
+
+    assert_eq!(
+        partition.disk_space(),
+        levels.iter().map(|level| level.size).sum::<u64>(),
+    );
+
+    Ok(())
+}
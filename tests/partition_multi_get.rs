@@ -0,0 +1,53 @@
+use fjall::{Config, KvSeparationOptions, PartitionCreateOptions};
+use test_log::test;
+
+#[test]
+fn partition_multi_get() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open()?;
+    let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+    let blobs = keyspace.open_partition(
+        "blobs",
+        PartitionCreateOptions::default().with_kv_separation(KvSeparationOptions::default()),
+    )?;
+
+    for x in 0..1_000u64 {
+        partition.insert(x.to_be_bytes(), x.to_le_bytes())?;
+        blobs.insert(x.to_be_bytes(), x.to_string().repeat(1_000))?;
+    }
+    partition.rotate_memtable_and_wait()?;
+    blobs.rotate_memtable_and_wait()?;
+
+    for x in (0..1_000u64).step_by(3) {
+        partition.remove(x.to_be_bytes())?;
+        blobs.remove(x.to_be_bytes())?;
+    }
+    for x in 1_000..1_100u64 {
+        partition.insert(x.to_be_bytes(), x.to_le_bytes())?;
+        blobs.insert(x.to_be_bytes(), x.to_string().repeat(1_000))?;
+    }
+
+    // NOTE: Unsorted, with duplicates and missing keys
+    let keys = (0..500u64)
+        .map(|x| (x * 7_919) % 1_200)
+        .chain([5, 5, 1_099, 5])
+        .map(u64::to_be_bytes)
+        .collect::<Vec<_>>();
+
+    for partition in [&partition, &blobs] {
+        let values = partition.multi_get(&keys)?;
+        assert_eq!(keys.len(), values.len());
+
+        for (key, value) in keys.iter().zip(values) {
+            assert_eq!(partition.get(key)?, value);
+        }
+    }
+
+    assert_eq!(
+        Vec::<Option<fjall::Slice>>::new(),
+        partition.multi_get::<&[u8]>(&[])?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use fjall::{AnyTree, CompressionType, Config, PartitionCreateOptions};
+use rand::RngCore;
+use test_log::test;
+
+const ITEM_COUNT: usize = 1_000;
+
+#[test]
+fn partition_no_compression_roundtrip() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open()?;
+    let partition = keyspace.open_partition(
+        "default",
+        PartitionCreateOptions::default().compression(CompressionType::None),
+    )?;
+
+    let mut rng = rand::thread_rng();
+
+    let items = (0..ITEM_COUNT)
+        .map(|idx| {
+            let mut value = vec![0; 256];
+            rng.fill_bytes(&mut value);
+            ((idx as u64).to_be_bytes(), value)
+        })
+        .collect::<Vec<_>>();
+
+    for (key, value) in &items {
+        partition.insert(key, value)?;
+    }
+    partition.rotate_memtable_and_wait()?;
+    assert_eq!(1, partition.segment_count());
+
+    for (key, value) in &items {
+        assert_eq!(Some(value.as_slice().into()), partition.get(key)?);
+    }
+
+    let AnyTree::Standard(tree) = &partition.tree else {
+        panic!("should be standard tree");
+    };
+
+    let levels = tree.levels.read().expect("lock is poisoned");
+    let segment = levels.iter().next().expect("segment should exist");
+    let meta = &segment.metadata;
+
+    assert_eq!(CompressionType::None, meta.compression);
+
+    // NOTE: Only block headers make up the difference
+    let diff = meta.file_size.abs_diff(meta.uncompressed_size);
+    assert!(diff * 20 < meta.uncompressed_size);
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use fjall::{Config, PartitionCreateOptions};
+use test_log::test;
+
+const ITEM_COUNT: u64 = 100;
+
+#[test]
+fn partition_orphan_segments_are_deleted_on_recovery() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let segments_folder = folder
+        .path()
+        .join("partitions")
+        .join("default")
+        .join("segments");
+
+    let segment_ids = {
+        let keyspace = Config::new(&folder).compaction_workers(0).open()?;
+        let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+        for x in 0..ITEM_COUNT {
+            partition.insert(x.to_be_bytes(), "abc")?;
+        }
+        partition.rotate_memtable_and_wait()?;
+
+        partition
+            .segment_summary()
+            .iter()
+            .map(|x| x.id)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(1, segment_ids.len());
+
+    // NOTE: Simulate a crash after a segment was written, but before it was registered
+    let orphan_path = segments_folder.join("999");
+    let unfinished_path = segments_folder.join("tmp_1000");
+    std::fs::copy(
+        segments_folder.join(segment_ids[0].to_string()),
+        &orphan_path,
+    )?;
+    std::fs::write(&unfinished_path, "unfinished")?;
+
+    for _ in 0..3 {
+        let keyspace = Config::new(&folder).compaction_workers(0).open()?;
+        let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+        assert!(!orphan_path.try_exists()?);
+        assert!(!unfinished_path.try_exists()?);
+
+        assert_eq!(
+            segment_ids,
+            partition
+                .segment_summary()
+                .iter()
+                .map(|x| x.id)
+                .collect::<Vec<_>>(),
+        );
+        assert_eq!(ITEM_COUNT as usize, partition.len()?);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use fjall::{Config, PartitionCreateOptions};
+use test_log::test;
+
+#[test]
+fn partition_point_read_oldest_segment() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open()?;
+    let partition = keyspace.open_partition(
+        "default",
+        PartitionCreateOptions::default().compaction_strategy(fjall::compaction::Strategy::Fifo(
+            fjall::compaction::Fifo::new(u64::MAX, None),
+        )),
+    )?;
+
+    partition.insert("a", "oldest")?;
+    partition.insert("b", "oldest")?;
+    partition.rotate_memtable_and_wait()?;
+
+    for idx in 0..10 {
+        partition.insert(format!("c{idx}"), "newer")?;
+        partition.insert("z", idx.to_string())?;
+
+        if idx == 5 {
+            partition.remove("b")?;
+        }
+
+        partition.rotate_memtable_and_wait()?;
+    }
+
+    assert_eq!(11, partition.segment_count());
+
+    assert_eq!(Some("oldest".as_bytes().into()), partition.get("a")?);
+    assert_eq!(None, partition.get("b")?);
+    assert_eq!(Some("9".as_bytes().into()), partition.get("z")?);
+    assert_eq!(None, partition.get("y")?);
+
+    let snapshot = partition.snapshot_at(3);
+    assert_eq!(Some("oldest".as_bytes().into()), snapshot.get("b")?);
+
+    Ok(())
+}
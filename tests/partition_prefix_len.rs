@@ -0,0 +1,55 @@
+use fjall::{Config, KvSeparationOptions, PartitionCreateOptions};
+use test_log::test;
+
+const ITEM_COUNT: usize = 100;
+
+fn check(partition: &fjall::PartitionHandle) -> fjall::Result<()> {
+    for x in 0..ITEM_COUNT {
+        partition.insert(format!("a#{x:0>3}"), "a".repeat(1_000))?;
+        partition.insert(format!("b#{x:0>3}"), "b".repeat(1_000))?;
+    }
+
+    assert_eq!(ITEM_COUNT, partition.prefix_len("a#")?);
+    assert_eq!(partition.prefix("a#").count(), partition.prefix_len("a#")?);
+
+    for x in (0..ITEM_COUNT).step_by(2) {
+        partition.remove(format!("a#{x:0>3}"))?;
+    }
+    assert_eq!(ITEM_COUNT / 2, partition.prefix_len("a#")?);
+    assert_eq!(partition.prefix("a#").count(), partition.prefix_len("a#")?);
+
+    partition.rotate_memtable_and_wait()?;
+
+    for x in (1..ITEM_COUNT).step_by(2) {
+        partition.remove(format!("a#{x:0>3}"))?;
+    }
+    assert_eq!(0, partition.prefix_len("a#")?);
+    assert_eq!(ITEM_COUNT, partition.prefix_len("b#")?);
+    assert_eq!(ITEM_COUNT, partition.prefix_len("")?);
+
+    Ok(())
+}
+
+#[test]
+fn partition_prefix_len() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open()?;
+    let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+    check(&partition)
+}
+
+#[test]
+fn partition_prefix_len_blob() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open()?;
+    let partition = keyspace.open_partition(
+        "default",
+        PartitionCreateOptions::default()
+            .with_kv_separation(KvSeparationOptions::default().separation_threshold(100)),
+    )?;
+
+    check(&partition)
+}
@@ -0,0 +1,82 @@
+use fjall::{Config, PartitionCreateOptions, UserKey};
+use test_log::test;
+
+const ITEM_COUNT: usize = 100;
+const PAGE_SIZE: usize = 10;
+
+#[test]
+fn partition_prefix_pagination() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open()?;
+    let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+    partition.insert("a", "")?;
+    for x in 0..ITEM_COUNT {
+        partition.insert(format!("user#{x:0>3}"), "")?;
+    }
+    partition.insert("z", "")?;
+
+    let expected = (0..ITEM_COUNT)
+        .map(|x| UserKey::from(format!("user#{x:0>3}").as_bytes()))
+        .collect::<Vec<_>>();
+
+    // Forward
+    let mut keys = partition
+        .prefix("user#")
+        .take(PAGE_SIZE)
+        .map(|kv| kv.map(|(k, _)| k))
+        .collect::<fjall::Result<Vec<_>>>()?;
+
+    loop {
+        let cursor = keys.last().expect("should exist").clone();
+
+        let page = partition
+            .prefix_after("user#", &cursor)
+            .take(PAGE_SIZE)
+            .map(|kv| kv.map(|(k, _)| k))
+            .collect::<fjall::Result<Vec<_>>>()?;
+
+        if page.is_empty() {
+            break;
+        }
+        assert_eq!(PAGE_SIZE, page.len());
+        keys.extend(page);
+    }
+    assert_eq!(expected, keys);
+
+    // Reverse
+    let mut keys = partition
+        .prefix("user#")
+        .rev()
+        .take(PAGE_SIZE)
+        .map(|kv| kv.map(|(k, _)| k))
+        .collect::<fjall::Result<Vec<_>>>()?;
+
+    loop {
+        let cursor = keys.last().expect("should exist").clone();
+
+        let page = partition
+            .prefix_before("user#", &cursor)
+            .rev()
+            .take(PAGE_SIZE)
+            .map(|kv| kv.map(|(k, _)| k))
+            .collect::<fjall::Result<Vec<_>>>()?;
+
+        if page.is_empty() {
+            break;
+        }
+        assert_eq!(PAGE_SIZE, page.len());
+        keys.extend(page);
+    }
+    keys.reverse();
+    assert_eq!(expected, keys);
+
+    // Cursor outside of prefix
+    assert_eq!(ITEM_COUNT, partition.prefix_after("user#", "a").count());
+    assert_eq!(0, partition.prefix_after("user#", "z").count());
+    assert_eq!(ITEM_COUNT, partition.prefix_before("user#", "z").count());
+    assert_eq!(0, partition.prefix_before("user#", "a").count());
+
+    Ok(())
+}
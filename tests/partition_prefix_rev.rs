@@ -0,0 +1,55 @@
+use fjall::{Config, PartitionCreateOptions};
+use test_log::test;
+
+const ITEM_COUNT: usize = 500;
+
+#[test]
+fn partition_prefix_rev_across_segments() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open()?;
+    let partition = keyspace.open_partition(
+        "default",
+        PartitionCreateOptions::default().block_size(1_024),
+    )?;
+
+    // NOTE: Interleave keys over multiple segments + the active memtable,
+    // so reverse iteration has to cross block and segment boundaries
+    for run in 0..4 {
+        for x in (run..ITEM_COUNT).step_by(4) {
+            partition.insert(format!("user:{x:0>5}"), "abc")?;
+            partition.insert(format!("post:{x:0>5}"), "abc")?;
+        }
+
+        if run < 3 {
+            partition.rotate_memtable_and_wait()?;
+        }
+    }
+
+    for x in (0..ITEM_COUNT).step_by(7) {
+        partition.remove(format!("user:{x:0>5}"))?;
+    }
+
+    let mut forward = partition
+        .prefix("user:")
+        .map(|kv| kv.map(|(k, _)| k))
+        .collect::<fjall::Result<Vec<_>>>()?;
+
+    let reverse = partition
+        .prefix("user:")
+        .rev()
+        .map(|kv| kv.map(|(k, _)| k))
+        .collect::<fjall::Result<Vec<_>>>()?;
+
+    assert_eq!(ITEM_COUNT - ITEM_COUNT.div_ceil(7), forward.len());
+    assert!(reverse.windows(2).all(|w| w[0] > w[1]));
+    assert_eq!(
+        Some(b"user:00499".as_slice()),
+        reverse.first().map(|k| &**k)
+    );
+
+    forward.reverse();
+    assert_eq!(forward, reverse);
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use fjall::{Config, PartitionCreateOptions, Slice};
+use std::ops::Bound;
+use test_log::test;
+
+fn keys(iter: impl Iterator<Item = fjall::Result<fjall::KvPair>>) -> fjall::Result<Vec<Slice>> {
+    iter.map(|kv| kv.map(|(k, _)| k)).collect()
+}
+
+#[test]
+fn partition_range_bounds() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open()?;
+    let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+    // NOTE: Spread keys over a segment, a sealed memtable and the active memtable
+    for key in ["a", "c", "e"] {
+        partition.insert(key, "abc")?;
+    }
+    partition.rotate_memtable_and_wait()?;
+
+    for key in ["b", "d"] {
+        partition.insert(key, "abc")?;
+    }
+    partition.rotate_memtable()?;
+
+    partition.insert("f", "abc")?;
+    partition.remove("e")?;
+
+    let all = ["a", "b", "c", "d", "f"]
+        .iter()
+        .map(|k| Slice::from(k.as_bytes()))
+        .collect::<Vec<_>>();
+
+    // Fully open
+    assert_eq!(all, keys(partition.range::<&str, _>(..))?);
+
+    // Half open
+    assert_eq!(&all[1..3], keys(partition.range("b".."d"))?);
+    assert_eq!(&all[1..4], keys(partition.range("b"..="d"))?);
+    assert_eq!(&all[2..], keys(partition.range("c"..))?);
+    assert_eq!(&all[..2], keys(partition.range(.."c"))?);
+    assert_eq!(
+        &all[2..],
+        keys(partition.range::<&str, _>((Bound::Excluded("b"), Bound::Unbounded)))?,
+    );
+
+    // Single key
+    assert_eq!(&all[3..4], keys(partition.range("d"..="d"))?);
+    assert!(keys(partition.range("d".."d"))?.is_empty());
+
+    // Deleted key
+    assert!(keys(partition.range("e"..="e"))?.is_empty());
+
+    // Snapshot
+    let snapshot = partition.snapshot();
+    partition.insert("bb", "abc")?;
+    assert_eq!(
+        &all[1..3],
+        keys(snapshot.range("b".."d").map(|kv| kv.map_err(Into::into)))?
+    );
+    assert_eq!(3, partition.range("b".."d").count());
+
+    Ok(())
+}
@@ -18,6 +18,7 @@ fn reload_partition_config() -> fjall::Result<()> {
                     fjall::compaction::SizeTiered::default(),
                 ))
                 .block_size(10_000)
+                .bloom_fp_rate(0.001)
                 .with_kv_separation(
                     KvSeparationOptions::default()
                         .separation_threshold(4_000)
@@ -0,0 +1,50 @@
+use fjall::{Config, PartitionCreateOptions};
+use test_log::test;
+
+#[test]
+fn partition_segment_summary() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open()?;
+    let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+    assert!(partition.segment_summary().is_empty());
+
+    let batches: [&[&str]; 3] = [&["a", "b", "c"], &["d", "e"], &["f", "g", "h", "i"]];
+
+    for keys in batches {
+        for key in keys {
+            partition.insert(key, "abc")?;
+        }
+        partition.rotate_memtable_and_wait()?;
+    }
+
+    let mut segments = partition.segment_summary();
+    assert_eq!(3, segments.len());
+
+    segments.sort_by_key(|x| x.id);
+
+    for (segment, keys) in segments.iter().zip(batches) {
+        assert_eq!(0, segment.level);
+        assert_eq!(keys.len() as u64, segment.item_count);
+        assert!(segment.size > 0);
+
+        let first = keys.first().expect("should exist");
+        let last = keys.last().expect("should exist");
+        assert_eq!(
+            (first.as_bytes().into(), last.as_bytes().into()),
+            segment.key_range
+        );
+    }
+
+    for pair in segments.windows(2) {
+        assert!(pair[0].seqnos.1 < pair[1].seqnos.0);
+    }
+
+    assert_eq!(
+        partition.disk_space(),
+        segments.iter().map(|x| x.size).sum::<u64>()
+    );
+
+    Ok(())
+}
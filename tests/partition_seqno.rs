@@ -0,0 +1,50 @@
+use fjall::{Config, PartitionCreateOptions};
+use test_log::test;
+
+#[test]
+fn partition_durable_and_current_seqno() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open()?;
+    let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+    let other = keyspace.open_partition("other", PartitionCreateOptions::default())?;
+
+    assert_eq!(None, partition.durable_seqno());
+    assert_eq!(None, partition.current_seqno());
+
+    for x in 0..10u64 {
+        partition.insert(x.to_be_bytes(), "abc")?;
+    }
+    assert_eq!(None, partition.durable_seqno());
+    assert_eq!(Some(keyspace.instant() - 1), partition.current_seqno());
+
+    partition.rotate_memtable_and_wait()?;
+    let durable = partition.durable_seqno();
+    assert_eq!(partition.current_seqno(), durable);
+
+    // NOTE: Writes to other partitions do not move the seqnos of this partition
+    other.insert("a", "abc")?;
+    assert_eq!(durable, partition.current_seqno());
+
+    for x in 0..10u64 {
+        partition.insert(x.to_be_bytes(), "def")?;
+    }
+    assert_eq!(durable, partition.durable_seqno());
+    assert!(partition.current_seqno() > durable);
+    assert_eq!(Some(keyspace.instant() - 1), partition.current_seqno());
+
+    partition.rotate_memtable_and_wait()?;
+    assert_eq!(partition.current_seqno(), partition.durable_seqno());
+
+    drop(partition);
+    drop(other);
+    drop(keyspace);
+
+    // NOTE: Durable seqno is recovered from segments
+    let keyspace = Config::new(&folder).open()?;
+    let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+    assert_eq!(partition.current_seqno(), partition.durable_seqno());
+    assert!(partition.durable_seqno() > durable);
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use fjall::{CompressionType, Config, PartitionCreateOptions, VerifyIssue};
+use std::path::{Path, PathBuf};
+use test_log::test;
+
+const ITEM_COUNT: u64 = 100;
+
+fn segment_file(folder: &Path) -> fjall::Result<PathBuf> {
+    let segments_folder = folder.join("partitions").join("default").join("segments");
+
+    let mut files = std::fs::read_dir(segments_folder)?
+        .map(|dirent| dirent.map(|dirent| dirent.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+
+    assert_eq!(1, files.len());
+    Ok(files.remove(0))
+}
+
+fn replace_in_file(path: &Path, needle: &[u8], replacement: &[u8]) -> fjall::Result<()> {
+    assert_eq!(needle.len(), replacement.len());
+
+    let mut bytes = std::fs::read(path)?;
+    let pos = bytes
+        .windows(needle.len())
+        .position(|window| window == needle)
+        .expect("should find bytes");
+    bytes[pos..pos + needle.len()].copy_from_slice(replacement);
+    std::fs::write(path, bytes)?;
+
+    Ok(())
+}
+
+fn write_segment(folder: &Path) -> fjall::Result<()> {
+    let keyspace = Config::new(folder).open()?;
+    let partition = keyspace.open_partition(
+        "default",
+        PartitionCreateOptions::default().compression(CompressionType::None),
+    )?;
+
+    for x in 0..ITEM_COUNT {
+        partition.insert(format!("key_{x:04}"), format!("value_{x:04}"))?;
+    }
+    partition.rotate_memtable_and_wait()?;
+
+    let report = partition.verify()?;
+    assert!(report.is_ok());
+    assert_eq!(1, report.segment_count);
+    assert_eq!(ITEM_COUNT, report.item_count);
+
+    Ok(())
+}
+
+#[test]
+fn partition_verify_corrupted_block() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+    write_segment(folder.path())?;
+
+    replace_in_file(&segment_file(folder.path())?, b"value_0042", b"value_9999")?;
+
+    let keyspace = Config::new(&folder).open()?;
+    let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+    let report = partition.verify()?;
+    assert!(!report.is_ok());
+    assert_eq!(1, report.corrupted_block_count);
+    assert_eq!(Vec::<VerifyIssue>::new(), report.issues);
+
+    Ok(())
+}
+
+#[test]
+fn partition_verify_unsorted_items() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+    write_segment(folder.path())?;
+
+    let segment_id = {
+        let path = segment_file(folder.path())?;
+        replace_in_file(&path, b"key_0042", b"key_9942")?;
+
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.parse::<u64>().ok())
+            .expect("should be segment ID")
+    };
+
+    let keyspace = Config::new(&folder).open()?;
+    let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+    let report = partition.verify()?;
+    assert!(!report.is_ok());
+    assert_eq!(1, report.corrupted_block_count);
+    assert_eq!(
+        vec![VerifyIssue::UnsortedItems {
+            segment_id,
+            position: 43,
+        }],
+        report.issues
+    );
+
+    Ok(())
+}
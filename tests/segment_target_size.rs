@@ -0,0 +1,51 @@
+use fjall::{CompressionType, Config, PartitionCreateOptions};
+use rand::RngCore;
+use test_log::test;
+
+const VALUE_SIZE: usize = 1_024;
+const ITEM_COUNT: u64 = 10 * 1_024;
+
+#[test]
+fn segment_target_size() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder)
+        .compaction_workers(0)
+        .segment_target_size(Some(/* 1 MiB */ 1_024 * 1_024))
+        .open()?;
+    let partition = keyspace.open_partition(
+        "default",
+        PartitionCreateOptions::default().compression(CompressionType::None),
+    )?;
+
+    let mut rng = rand::thread_rng();
+    let mut value = vec![0; VALUE_SIZE];
+
+    // NOTE: 10 MiB of incompressible data in 4 L0 segments
+    for chunk in 0..4 {
+        for x in (chunk..ITEM_COUNT).step_by(4) {
+            rng.fill_bytes(&mut value);
+            partition.insert(x.to_be_bytes(), &value)?;
+        }
+        partition.rotate_memtable_and_wait()?;
+    }
+
+    assert_eq!(4, partition.segment_count());
+
+    partition.compact_manually()?;
+
+    let segment_count = partition.segment_count();
+    assert!(
+        (9..=12).contains(&segment_count),
+        "expected roughly 10 segments, got {segment_count}"
+    );
+    assert_eq!(ITEM_COUNT as usize, partition.len()?);
+
+    Ok(())
+}
+
+#[test]
+#[should_panic = "segment target size is too small"]
+fn segment_target_size_too_small() {
+    let _ = Config::default().segment_target_size(Some(1_024));
+}
@@ -0,0 +1,58 @@
+use fjall::{compaction::Strategy, Config, PartitionCreateOptions};
+use test_log::test;
+
+const ITEM_COUNT: u64 = 100;
+
+#[test]
+fn snapshot_survives_compaction() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).compaction_workers(0).open()?;
+    let partition = keyspace.open_partition(
+        "default",
+        PartitionCreateOptions::default().compaction_strategy(Strategy::Leveled(
+            fjall::compaction::Leveled {
+                l0_threshold: 2,
+                ..Default::default()
+            },
+        )),
+    )?;
+
+    let item_count = |partition: &fjall::PartitionHandle| -> u64 {
+        partition
+            .segment_summary()
+            .iter()
+            .map(|x| x.item_count)
+            .sum()
+    };
+
+    for x in 0..ITEM_COUNT {
+        partition.insert(x.to_be_bytes(), "old")?;
+    }
+    partition.rotate_memtable_and_wait()?;
+
+    let snapshot = partition.snapshot();
+
+    for x in 0..ITEM_COUNT {
+        if x % 2 == 0 {
+            partition.insert(x.to_be_bytes(), "new")?;
+        } else {
+            partition.remove(x.to_be_bytes())?;
+        }
+    }
+    partition.rotate_memtable_and_wait()?;
+
+    let report = partition.compact_manually()?;
+    assert_eq!(2, report.segments_merged);
+
+    // NOTE: Versions needed by the open snapshot are kept
+    assert_eq!(2 * ITEM_COUNT, item_count(&partition));
+
+    for x in 0..ITEM_COUNT {
+        assert_eq!(Some(b"old".into()), snapshot.get(x.to_be_bytes())?);
+    }
+    assert_eq!(ITEM_COUNT as usize, snapshot.len()?);
+    assert_eq!(ITEM_COUNT as usize / 2, partition.len()?);
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use fjall::{Config, PartitionCreateOptions};
+use test_log::test;
+
+#[test]
+fn snapshot_limit() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).max_open_snapshots(3).open()?;
+    let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+    partition.insert("a", "abc")?;
+
+    let mut snapshots = (0..3)
+        .map(|_| partition.try_snapshot())
+        .collect::<fjall::Result<Vec<_>>>()?;
+
+    assert!(matches!(
+        partition.try_snapshot(),
+        Err(fjall::Error::SnapshotLimitReached)
+    ));
+
+    snapshots.pop();
+
+    let snapshot = partition.try_snapshot()?;
+    assert_eq!(1, snapshot.len()?);
+
+    assert!(matches!(
+        partition.try_snapshot(),
+        Err(fjall::Error::SnapshotLimitReached)
+    ));
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "ssi_tx")]
+fn snapshot_limit_after_tx_commit() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder)
+        .max_open_snapshots(4)
+        .open_transactional()?;
+    let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+    let mut tx = keyspace.write_tx()?;
+    tx.insert(&partition, "a", "abc");
+    assert!(tx.commit()?.is_ok());
+
+    // NOTE: The committed transaction must release its snapshot exactly once
+    let snapshots = (0..4)
+        .map(|_| partition.inner().try_snapshot())
+        .collect::<fjall::Result<Vec<_>>>()?;
+
+    assert!(matches!(
+        partition.inner().try_snapshot(),
+        Err(fjall::Error::SnapshotLimitReached)
+    ));
+
+    drop(snapshots);
+    assert_eq!(1, partition.inner().try_snapshot()?.len()?);
+
+    Ok(())
+}
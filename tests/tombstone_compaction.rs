@@ -0,0 +1,58 @@
+use fjall::{compaction::Strategy, Config, PartitionCreateOptions};
+use test_log::test;
+
+const ITEM_COUNT: u64 = 100;
+
+#[test]
+fn tombstone_survives_non_bottom_compaction() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let create_options = || {
+        PartitionCreateOptions::default().compaction_strategy(Strategy::Leveled(
+            fjall::compaction::Leveled {
+                l0_threshold: 2,
+                ..Default::default()
+            },
+        ))
+    };
+
+    {
+        let keyspace = Config::new(&folder).compaction_workers(0).open()?;
+        let partition = keyspace.open_partition("default", create_options())?;
+
+        for _ in 0..2 {
+            for x in 0..ITEM_COUNT {
+                partition.insert(x.to_be_bytes(), "abc")?;
+            }
+            partition.rotate_memtable_and_wait()?;
+        }
+        partition.compact_manually()?;
+
+        for x in (0..ITEM_COUNT).step_by(2) {
+            partition.remove(x.to_be_bytes())?;
+        }
+        partition.rotate_memtable_and_wait()?;
+        partition.insert(ITEM_COUNT.to_be_bytes(), "abc")?;
+        partition.rotate_memtable_and_wait()?;
+
+        let report = partition.compact_manually()?;
+        assert!(report.segments_merged > 0);
+
+        for x in 0..ITEM_COUNT {
+            assert_eq!(x % 2 == 1, partition.contains_key(x.to_be_bytes())?);
+        }
+        assert_eq!(ITEM_COUNT as usize / 2 + 1, partition.len()?);
+    }
+
+    {
+        let keyspace = Config::new(&folder).compaction_workers(0).open()?;
+        let partition = keyspace.open_partition("default", create_options())?;
+
+        for x in 0..ITEM_COUNT {
+            assert_eq!(x % 2 == 1, partition.contains_key(x.to_be_bytes())?);
+        }
+        assert_eq!(ITEM_COUNT as usize / 2 + 1, partition.len()?);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+#![cfg(any(feature = "single_writer_tx", feature = "ssi_tx"))]
+
+use fjall::{Config, PartitionCreateOptions};
+use test_log::test;
+
+#[test]
+fn tx_read_at() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open_transactional()?;
+    let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+    partition.insert("a", "old")?;
+    partition.insert("b", "old")?;
+    let instant = keyspace.instant();
+
+    partition.insert("a", "new")?;
+    partition.remove("b")?;
+    partition.insert("c", "new")?;
+
+    let tx = keyspace.read_tx_at(instant)?;
+    assert_eq!(Some("old".as_bytes().into()), tx.get(&partition, "a")?);
+    assert_eq!(Some("old".as_bytes().into()), tx.get(&partition, "b")?);
+    assert_eq!(None, tx.get(&partition, "c")?);
+    assert_eq!(2, tx.len(&partition)?);
+
+    let tx = keyspace.read_tx();
+    assert_eq!(Some("new".as_bytes().into()), tx.get(&partition, "a")?);
+    assert_eq!(None, tx.get(&partition, "b")?);
+    assert_eq!(2, tx.len(&partition)?);
+
+    Ok(())
+}
+
+#[test]
+fn tx_read_at_too_old() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder)
+        .snapshot_safety_gap(1)
+        .open_transactional()?;
+    let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+    partition.insert("a", "old")?;
+    let instant = keyspace.instant();
+
+    for _ in 0..10 {
+        partition.insert("a", "new")?;
+    }
+
+    let watermark = keyspace.inner().gc_now();
+    assert!(watermark > instant);
+
+    assert!(matches!(
+        keyspace.read_tx_at(instant),
+        Err(fjall::Error::SnapshotTooOld)
+    ));
+
+    let tx = keyspace.read_tx_at(watermark)?;
+    assert_eq!(Some("new".as_bytes().into()), tx.get(&partition, "a")?);
+
+    Ok(())
+}
+
+#[test]
+#[should_panic = "instant should not be in the future"]
+fn tx_read_at_future() {
+    let folder = tempfile::tempdir().unwrap();
+    let keyspace = Config::new(&folder).open_transactional().unwrap();
+
+    let _ = keyspace.read_tx_at(keyspace.instant() + 1);
+}
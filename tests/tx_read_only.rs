@@ -0,0 +1,47 @@
+#![cfg(feature = "single_writer_tx")]
+
+use fjall::{Config, PartitionCreateOptions};
+use test_log::test;
+
+const THREADS: usize = 8;
+const READS_PER_THREAD: usize = 1_000;
+
+#[test]
+fn tx_read_only_does_not_block_on_writer() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open_transactional()?;
+    let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+    partition.insert("a", "old")?;
+
+    // NOTE: Hold the write transaction (and thus the writer lock) while reading
+    let mut write_tx = keyspace.write_tx();
+    write_tx.insert(&partition, "a", "new");
+
+    let handles = (0..THREADS)
+        .map(|_| {
+            let keyspace = keyspace.clone();
+            let partition = partition.clone();
+
+            std::thread::spawn(move || -> fjall::Result<()> {
+                for _ in 0..READS_PER_THREAD {
+                    let tx = keyspace.read_tx();
+                    assert_eq!(Some("old".as_bytes().into()), tx.get(&partition, "a")?);
+                }
+                Ok(())
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for handle in handles {
+        handle.join().expect("thread should not panic")?;
+    }
+
+    write_tx.commit()?;
+
+    let tx = keyspace.read_tx();
+    assert_eq!(Some("new".as_bytes().into()), tx.get(&partition, "a")?);
+
+    Ok(())
+}
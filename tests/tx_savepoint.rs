@@ -0,0 +1,74 @@
+#![cfg(feature = "single_writer_tx")]
+
+use fjall::{Config, PartitionCreateOptions};
+use test_log::test;
+
+#[test]
+fn tx_savepoint() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open_transactional()?;
+    let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+    partition.insert("c", "old")?;
+
+    let mut tx = keyspace.write_tx();
+    tx.insert(&partition, "a", "a");
+
+    let savepoint = tx.savepoint();
+    tx.insert(&partition, "b", "b");
+    tx.remove(&partition, "c");
+    tx.insert(&partition, "a", "new");
+    assert!(!tx.contains_key(&partition, "c")?);
+
+    tx.rollback_to(&savepoint);
+    assert_eq!(b"a", &*tx.get(&partition, "a")?.expect("should exist"));
+    assert!(!tx.contains_key(&partition, "b")?);
+    assert!(tx.contains_key(&partition, "c")?);
+
+    tx.commit()?;
+
+    assert_eq!(b"a", &*partition.get("a")?.expect("should exist"));
+    assert!(!partition.contains_key("b")?);
+    assert_eq!(b"old", &*partition.get("c")?.expect("should exist"));
+
+    Ok(())
+}
+
+#[test]
+fn tx_savepoint_nested() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let keyspace = Config::new(&folder).open_transactional()?;
+    let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+    let mut tx = keyspace.write_tx();
+    tx.insert(&partition, "a", "a");
+
+    let outer = tx.savepoint();
+    tx.insert(&partition, "b", "b");
+
+    let inner = tx.savepoint();
+    tx.insert(&partition, "c", "c");
+
+    tx.rollback_to(&inner);
+    assert!(tx.contains_key(&partition, "b")?);
+    assert!(!tx.contains_key(&partition, "c")?);
+
+    // NOTE: Writes after rolling back must not leak into the savepoint
+    tx.insert(&partition, "d", "d");
+    tx.rollback_to(&inner);
+    assert!(!tx.contains_key(&partition, "d")?);
+
+    tx.rollback_to(&outer);
+    assert!(!tx.contains_key(&partition, "b")?);
+
+    tx.insert(&partition, "e", "e");
+    tx.commit()?;
+
+    assert_eq!(2, partition.inner().len()?);
+    assert!(partition.contains_key("a")?);
+    assert!(partition.contains_key("e")?);
+
+    Ok(())
+}
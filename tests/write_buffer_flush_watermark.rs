@@ -0,0 +1,54 @@
+use fjall::{Config, PartitionCreateOptions};
+use std::time::{Duration, Instant};
+use test_log::test;
+
+const ITEM_COUNT: usize = 300;
+
+fn fill(config: Config) -> fjall::Result<usize> {
+    let keyspace = config.max_write_buffer_size(1_024 * 1_024).open()?;
+    let partition = keyspace.open_partition("default", PartitionCreateOptions::default())?;
+
+    // NOTE: ~300 KiB, so between 25% and 50% of the write buffer
+    for idx in 0..ITEM_COUNT {
+        partition.insert(idx.to_be_bytes(), [0; 1_024])?;
+    }
+
+    let start = Instant::now();
+    while partition.segment_count() == 0 && start.elapsed() < Duration::from_secs(3) {
+        std::thread::sleep(Duration::from_millis(50));
+    }
+
+    Ok(partition.segment_count())
+}
+
+#[test]
+fn write_buffer_flush_watermark_low() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let segment_count = fill(Config::new(&folder).write_buffer_flush_watermark(0.25))?;
+    assert!(segment_count > 0);
+
+    Ok(())
+}
+
+#[test]
+fn write_buffer_flush_watermark_default() -> fjall::Result<()> {
+    let folder = tempfile::tempdir()?;
+
+    let segment_count = fill(Config::new(&folder))?;
+    assert_eq!(0, segment_count);
+
+    Ok(())
+}
+
+#[test]
+#[should_panic = "flush watermark should be in (0.0, 1.0]"]
+fn write_buffer_flush_watermark_zero() {
+    let _ = Config::new("").write_buffer_flush_watermark(0.0);
+}
+
+#[test]
+#[should_panic = "flush watermark should be in (0.0, 1.0]"]
+fn journal_flush_watermark_too_high() {
+    let _ = Config::new("").journal_flush_watermark(1.5);
+}